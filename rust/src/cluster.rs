@@ -0,0 +1,523 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream, UnixListener, UnixStream};
+use tokio::sync::{mpsc, oneshot, Mutex, Notify};
+
+use crate::config::ClusterConfig;
+
+const ADMIT_TIMEOUT: Duration = Duration::from_millis(500);
+/// How long a follower holds an admit decision open while waiting for a new leader to
+/// be elected (e.g. right after the old leader's connection drops), instead of
+/// immediately failing open. This is what "buffers" an event across the gap: the event
+/// isn't reported until it's either been flushed through the newly-elected leader's
+/// dedup set or this grace period runs out.
+const GAP_FLUSH_GRACE: Duration = Duration::from_secs(2);
+
+/// Hashes a (already-scrubbed) matched line the same way on every instance, so peers can
+/// agree on event identity without shipping the line body itself across the wire.
+pub fn hash_line(line: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    line.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Leader,
+    Follower,
+}
+
+/// Wire messages exchanged between cluster peers, modeled on Ceph's monitor
+/// probe/rank-comparison handshake. Every instance already observes the same matched
+/// lines locally (e.g. a shared NFS log tailed by several hosts), so rather than
+/// shipping line bodies around, peers only coordinate *which* instance is allowed to
+/// actually report a given (monitor, line) to Sentry: the leader keeps the
+/// authoritative sliding-window dedup set and answers `AdmitRequest`s from followers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Frame {
+    Probe { rank: u32, epoch: u64, process_id: u32 },
+    AdmitRequest { id: u64, monitor_name: String, line_hash: u64 },
+    AdmitResponse { id: u64, allow: bool },
+}
+
+struct PendingRequests {
+    next_id: u64,
+    waiters: HashMap<u64, oneshot::Sender<bool>>,
+}
+
+#[derive(Clone)]
+struct PeerLink {
+    tx: mpsc::UnboundedSender<Frame>,
+    pending: Arc<Mutex<PendingRequests>>,
+}
+
+impl PeerLink {
+    async fn request_admit(&self, monitor_name: &str, line_hash: u64) -> Option<bool> {
+        let (tx, rx) = oneshot::channel();
+        let id = {
+            let mut pending = self.pending.lock().await;
+            let id = pending.next_id;
+            pending.next_id += 1;
+            pending.waiters.insert(id, tx);
+            id
+        };
+
+        if self
+            .tx
+            .send(Frame::AdmitRequest {
+                id,
+                monitor_name: monitor_name.to_string(),
+                line_hash,
+            })
+            .is_err()
+        {
+            return None;
+        }
+
+        tokio::time::timeout(ADMIT_TIMEOUT, rx).await.ok()?.ok()
+    }
+}
+
+struct PeerInfo {
+    rank: u32,
+    process_id: u32,
+    link: PeerLink,
+}
+
+/// Cross-instance quorum state shared by all peer-connection tasks. The member with the
+/// lowest `(rank, process_id)` among currently-connected peers (including itself) is
+/// leader for the current epoch; ties on `rank` (e.g. a misconfiguration that gives two
+/// instances the same rank) are broken deterministically by the lower `process_id`,
+/// Ceph-monitor style, so exactly one of them ever claims leadership. Losing or gaining
+/// the leader link bumps the epoch and re-runs the comparison.
+struct Inner {
+    config: ClusterConfig,
+    process_id: u32,
+    role: Mutex<Role>,
+    epoch: Mutex<u64>,
+    /// Notified every time `recompute_role` runs, whether or not the role actually
+    /// changed, so `admit`'s gap-flush wait can wake up and re-check the leader link as
+    /// soon as a new one might be available.
+    role_changed: Notify,
+    peers: Mutex<HashMap<u64, PeerInfo>>,
+    /// The current leader's link, tagged with the epoch at which `recompute_role`
+    /// installed it. `admit` only trusts a cached link while its tag still matches the
+    /// current epoch, so a link left over from a stale election round is never used.
+    leader_link: Mutex<Option<(u64, PeerLink)>>,
+    dedup: Mutex<HashMap<(String, u64), Instant>>,
+    dedup_window: Duration,
+    next_conn_id: AtomicU64,
+}
+
+/// A cheaply-cloneable handle onto the cluster subsystem, consulted by `monitor::Monitor`
+/// before it reports a matched event to Sentry.
+#[derive(Clone)]
+pub struct ClusterHandle {
+    inner: Arc<Inner>,
+}
+
+impl ClusterHandle {
+    /// Binds the configured listen address, starts accepting peer connections, and
+    /// dials every configured peer. Connection failures to individual peers are logged
+    /// and retried by nothing more than the peer eventually dialing back in; they don't
+    /// fail startup.
+    pub async fn start(config: ClusterConfig) -> Result<Self> {
+        let dedup_window = Duration::from_secs(config.dedup_window_secs.max(1));
+        let process_id = std::process::id();
+
+        let inner = Arc::new(Inner {
+            role: Mutex::new(Role::Leader),
+            epoch: Mutex::new(0),
+            role_changed: Notify::new(),
+            peers: Mutex::new(HashMap::new()),
+            leader_link: Mutex::new(None),
+            dedup: Mutex::new(HashMap::new()),
+            dedup_window,
+            next_conn_id: AtomicU64::new(0),
+            process_id,
+            config: config.clone(),
+        });
+
+        let handle = Self { inner };
+        handle.spawn_listener().await?;
+
+        for peer in config.peers.clone() {
+            let handle = handle.clone();
+            tokio::spawn(async move {
+                if let Err(e) = handle.dial(peer.clone()).await {
+                    tracing::warn!("cluster: failed to connect to peer {}: {}", peer, e);
+                }
+            });
+        }
+
+        Ok(handle)
+    }
+
+    async fn spawn_listener(&self) -> Result<()> {
+        let inner = self.inner.clone();
+        let listen = self.inner.config.listen.clone();
+
+        if let Some(path) = listen.strip_prefix("unix:") {
+            let path = path.to_string();
+            let _ = std::fs::remove_file(&path);
+            let listener = UnixListener::bind(&path)
+                .with_context(|| format!("failed to bind cluster unix socket {:?}", path))?;
+
+            tokio::spawn(async move {
+                loop {
+                    match listener.accept().await {
+                        Ok((stream, _)) => spawn_connection(inner.clone(), stream),
+                        Err(e) => tracing::warn!("cluster: accept error on {:?}: {}", path, e),
+                    }
+                }
+            });
+        } else {
+            let addr = listen.strip_prefix("tcp:").unwrap_or(&listen).to_string();
+            let listener = TcpListener::bind(&addr)
+                .await
+                .with_context(|| format!("failed to bind cluster tcp listener {:?}", addr))?;
+
+            tokio::spawn(async move {
+                loop {
+                    match listener.accept().await {
+                        Ok((stream, _)) => spawn_connection(inner.clone(), stream),
+                        Err(e) => tracing::warn!("cluster: accept error on {:?}: {}", addr, e),
+                    }
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn dial(&self, addr: String) -> Result<()> {
+        if let Some(path) = addr.strip_prefix("unix:") {
+            let stream = UnixStream::connect(path)
+                .await
+                .with_context(|| format!("failed to connect to {:?}", path))?;
+            spawn_connection(self.inner.clone(), stream);
+        } else {
+            let tcp_addr = addr.strip_prefix("tcp:").unwrap_or(&addr);
+            let stream = TcpStream::connect(tcp_addr)
+                .await
+                .with_context(|| format!("failed to connect to {:?}", tcp_addr))?;
+            spawn_connection(self.inner.clone(), stream);
+        }
+        Ok(())
+    }
+
+    /// Decides whether this instance should report `(monitor_name, line_hash)` to
+    /// Sentry. Leaders dedup locally; followers ask the current leader link. If there is
+    /// no (trustworthy) leader link right now — the old leader's connection just
+    /// dropped and re-election hasn't finished, or the request to it timed out — the
+    /// event is held via `admit_after_gap` for up to `GAP_FLUSH_GRACE` waiting for a new
+    /// leader, rather than immediately failing open, so the gap doesn't produce a
+    /// guaranteed duplicate.
+    pub async fn admit(&self, monitor_name: &str, line_hash: u64) -> bool {
+        let role = *self.inner.role.lock().await;
+        match role {
+            Role::Leader => admit_locally(&self.inner, monitor_name, line_hash).await,
+            Role::Follower => match self.current_leader_link().await {
+                Some(link) => match link.request_admit(monitor_name, line_hash).await {
+                    Some(allow) => allow,
+                    None => admit_after_gap(&self.inner, monitor_name, line_hash).await,
+                },
+                None => admit_after_gap(&self.inner, monitor_name, line_hash).await,
+            },
+        }
+    }
+
+    /// Returns the current leader link, but only if it was installed at the epoch we're
+    /// still in — a link tagged with a stale epoch means a new election has started (or
+    /// finished) since it was cached and it's no longer safe to assume it still points
+    /// at the leader.
+    async fn current_leader_link(&self) -> Option<PeerLink> {
+        let current_epoch = *self.inner.epoch.lock().await;
+        let cached = self.inner.leader_link.lock().await.clone();
+        match cached {
+            Some((epoch, link)) if epoch == current_epoch => Some(link),
+            _ => None,
+        }
+    }
+}
+
+/// Holds an admit decision open across a leader-election gap instead of failing open
+/// immediately: waits (up to `GAP_FLUSH_GRACE`) for `recompute_role` to signal that the
+/// election state changed, and on each wake retries against whatever leader link is now
+/// current. This is what flushes events buffered during the gap through the newly
+/// elected leader's dedup set. Only once the grace period is exhausted without a usable
+/// leader link do we fall back to admitting locally, logging that this may duplicate.
+async fn admit_after_gap(inner: &Arc<Inner>, monitor_name: &str, line_hash: u64) -> bool {
+    let deadline = tokio::time::Instant::now() + GAP_FLUSH_GRACE;
+
+    loop {
+        let role = *inner.role.lock().await;
+        if role == Role::Leader {
+            return admit_locally(inner, monitor_name, line_hash).await;
+        }
+
+        let current_epoch = *inner.epoch.lock().await;
+        let cached = inner.leader_link.lock().await.clone();
+        if let Some((epoch, link)) = cached {
+            if epoch == current_epoch {
+                if let Some(allow) = link.request_admit(monitor_name, line_hash).await {
+                    return allow;
+                }
+            }
+        }
+
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        let _ = tokio::time::timeout(remaining, inner.role_changed.notified()).await;
+    }
+
+    tracing::warn!(
+        "cluster: no leader available for {} after {:?}, admitting locally (may duplicate)",
+        monitor_name,
+        GAP_FLUSH_GRACE
+    );
+    admit_locally(inner, monitor_name, line_hash).await
+}
+
+async fn admit_locally(inner: &Inner, monitor_name: &str, line_hash: u64) -> bool {
+    let mut dedup = inner.dedup.lock().await;
+    let now = Instant::now();
+    dedup.retain(|_, seen| now.duration_since(*seen) < inner.dedup_window);
+
+    let key = (monitor_name.to_string(), line_hash);
+    if dedup.contains_key(&key) {
+        false
+    } else {
+        dedup.insert(key, now);
+        true
+    }
+}
+
+fn spawn_connection<S>(inner: Arc<Inner>, stream: S)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let conn_id = inner.next_conn_id.fetch_add(1, Ordering::SeqCst);
+
+    tokio::spawn(async move {
+        if let Err(e) = run_connection(inner.clone(), conn_id, stream).await {
+            tracing::warn!("cluster: connection {} closed: {}", conn_id, e);
+        }
+        inner.peers.lock().await.remove(&conn_id);
+        recompute_role(&inner).await;
+    });
+}
+
+async fn run_connection<S>(inner: Arc<Inner>, conn_id: u64, stream: S) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (read_half, mut write_half) = tokio::io::split(stream);
+    let mut reader = BufReader::new(read_half).lines();
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<Frame>();
+    let pending = Arc::new(Mutex::new(PendingRequests {
+        next_id: 0,
+        waiters: HashMap::new(),
+    }));
+
+    tokio::spawn(async move {
+        while let Some(frame) = rx.recv().await {
+            let Ok(mut line) = serde_json::to_string(&frame) else {
+                continue;
+            };
+            line.push('\n');
+            if write_half.write_all(line.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let probe = Frame::Probe {
+        rank: inner.config.rank,
+        epoch: *inner.epoch.lock().await,
+        process_id: inner.process_id,
+    };
+    tx.send(probe).ok();
+
+    let link = PeerLink {
+        tx: tx.clone(),
+        pending: pending.clone(),
+    };
+
+    while let Some(raw) = reader.next_line().await? {
+        let frame: Frame = match serde_json::from_str(&raw) {
+            Ok(f) => f,
+            Err(e) => {
+                tracing::warn!("cluster: dropping malformed frame: {}", e);
+                continue;
+            }
+        };
+
+        match frame {
+            Frame::Probe { rank, epoch, process_id } => {
+                {
+                    let mut our_epoch = inner.epoch.lock().await;
+                    if epoch > *our_epoch {
+                        *our_epoch = epoch;
+                    }
+                }
+                inner.peers.lock().await.insert(
+                    conn_id,
+                    PeerInfo {
+                        rank,
+                        process_id,
+                        link: link.clone(),
+                    },
+                );
+                recompute_role(&inner).await;
+            }
+            Frame::AdmitRequest { id, monitor_name, line_hash } => {
+                let allow = admit_locally(&inner, &monitor_name, line_hash).await;
+                tx.send(Frame::AdmitResponse { id, allow }).ok();
+            }
+            Frame::AdmitResponse { id, allow } => {
+                if let Some(waiter) = pending.lock().await.waiters.remove(&id) {
+                    let _ = waiter.send(allow);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Recomputes leadership from the set of currently-connected peers: we're leader iff
+/// our own `(rank, process_id)` is the lowest among ourselves and every connected peer,
+/// so two instances that end up configured with the same rank still agree on exactly
+/// one leader instead of each electing itself. Losing or gaining the leader link bumps
+/// the epoch so a new quorum round is unambiguous to peers, and wakes anyone in
+/// `admit_after_gap` waiting to retry against the (possibly new) leader link.
+async fn recompute_role(inner: &Inner) {
+    let peers = inner.peers.lock().await;
+    let lowest_peer = peers
+        .values()
+        .min_by_key(|p| (p.rank, p.process_id));
+
+    let our_key = (inner.config.rank, inner.process_id);
+    let (new_role, new_leader_link) = match lowest_peer {
+        Some(peer) if (peer.rank, peer.process_id) < our_key => {
+            (Role::Follower, Some(peer.link.clone()))
+        }
+        _ => (Role::Leader, None),
+    };
+
+    let leader_changed = match (inner.leader_link.lock().await.as_ref(), new_leader_link.as_ref()) {
+        (Some((_, old)), Some(new)) => !old.tx.same_channel(&new.tx),
+        (None, None) => false,
+        _ => true,
+    };
+
+    let mut role = inner.role.lock().await;
+    let mut epoch = inner.epoch.lock().await;
+    if *role != new_role || leader_changed {
+        *epoch += 1;
+    }
+    if *role != new_role {
+        tracing::info!(
+            "cluster: role changed to {:?} (rank={}, process_id={})",
+            new_role,
+            inner.config.rank,
+            inner.process_id
+        );
+    }
+    *role = new_role;
+    *inner.leader_link.lock().await = new_leader_link.map(|link| (*epoch, link));
+    drop(epoch);
+    drop(role);
+    drop(peers);
+    inner.role_changed.notify_waiters();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_line_is_deterministic_and_content_sensitive() {
+        assert_eq!(hash_line("error: disk full"), hash_line("error: disk full"));
+        assert_ne!(hash_line("error: disk full"), hash_line("error: disk ok"));
+    }
+
+    fn test_inner(rank: u32, process_id: u32) -> Inner {
+        Inner {
+            config: ClusterConfig {
+                rank,
+                listen: "unix:/tmp/unused".to_string(),
+                peers: Vec::new(),
+                dedup_window_secs: 60,
+            },
+            process_id,
+            role: Mutex::new(Role::Leader),
+            epoch: Mutex::new(0),
+            role_changed: Notify::new(),
+            peers: Mutex::new(HashMap::new()),
+            leader_link: Mutex::new(None),
+            dedup: Mutex::new(HashMap::new()),
+            dedup_window: Duration::from_secs(60),
+            next_conn_id: AtomicU64::new(0),
+        }
+    }
+
+    fn test_peer_link() -> PeerLink {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        PeerLink {
+            tx,
+            pending: Arc::new(Mutex::new(PendingRequests {
+                next_id: 0,
+                waiters: HashMap::new(),
+            })),
+        }
+    }
+
+    #[tokio::test]
+    async fn recompute_role_breaks_equal_rank_ties_by_process_id() {
+        let inner = test_inner(/* rank */ 5, /* process_id */ 200);
+        inner.peers.lock().await.insert(
+            0,
+            PeerInfo {
+                rank: 5,
+                process_id: 100,
+                link: test_peer_link(),
+            },
+        );
+
+        recompute_role(&inner).await;
+
+        assert_eq!(*inner.role.lock().await, Role::Follower);
+        assert!(inner.leader_link.lock().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn recompute_role_stays_leader_when_our_process_id_is_lower() {
+        let inner = test_inner(/* rank */ 5, /* process_id */ 100);
+        inner.peers.lock().await.insert(
+            0,
+            PeerInfo {
+                rank: 5,
+                process_id: 200,
+                link: test_peer_link(),
+            },
+        );
+
+        recompute_role(&inner).await;
+
+        assert_eq!(*inner.role.lock().await, Role::Leader);
+        assert!(inner.leader_link.lock().await.is_none());
+    }
+}
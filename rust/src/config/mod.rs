@@ -1,7 +1,14 @@
+pub mod source;
+pub mod watcher;
+
+pub use source::ConfigSource;
+pub use watcher::ConfigWatcher;
+
 use anyhow::{Context, Result};
 use clap::Parser;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SentryConfig {
@@ -35,18 +42,222 @@ pub struct MonitorConfig {
     pub rate_limit_burst: Option<u32>,
     #[serde(default)]
     pub rate_limit_window: Option<String>,
+    /// For `type = "file"`: keep tailing the file after EOF (`tail -F` semantics)
+    /// instead of relying on the monitor's blind restart loop.
+    #[serde(default)]
+    pub follow: bool,
+    /// Number of preceding non-matching lines to attach as Sentry breadcrumbs on a
+    /// match. Defaults to `Monitor`'s own default (20) when unset.
+    #[serde(default)]
+    pub context_lines: Option<usize>,
 }
 
 fn default_pattern() -> String {
     "Error".to_string()
 }
 
+/// Names one of `count` CLI-constructed monitors of the same source `kind`: the bare kind
+/// when there's only one (preserving existing single-source names like "file"), else
+/// `kind-1`, `kind-2`, ... in the order given on the command line.
+fn source_name(kind: &str, index: usize, count: usize) -> String {
+    if count <= 1 {
+        kind.to_string()
+    } else {
+        format!("{}-{}", kind, index + 1)
+    }
+}
+
+/// Validates a monitor's rate-limit settings at config-load time, rather than letting a
+/// nonsensical value (a zero-duration window, or a burst of 0) silently disable limiting
+/// once `Monitor::new` builds the token bucket from it.
+fn validate_rate_limit(mon_cfg: &MonitorConfig) -> Result<()> {
+    if let Some(burst) = mon_cfg.rate_limit_burst {
+        if burst < 1 {
+            anyhow::bail!(
+                "monitor '{}': rate_limit_burst must be at least 1",
+                mon_cfg.name
+            );
+        }
+    }
+
+    if let Some(window) = &mon_cfg.rate_limit_window {
+        if crate::monitor::parse_window(window).is_zero() {
+            anyhow::bail!(
+                "monitor '{}': rate_limit_window '{}' must parse to a non-zero duration \
+                 (e.g. \"30s\", \"5m\", \"1h\", \"250ms\")",
+                mon_cfg.name,
+                window
+            );
+        }
+    }
+
+    Ok(())
+}
+
+impl MonitorConfig {
+    /// Whether switching from `self` to `other` changes anything about how this monitor
+    /// behaves, and so should be cancelled and rebuilt from scratch by
+    /// `manager::MonitorManager::reconcile` rather than left running untouched.
+    pub fn needs_respawn(&self, other: &MonitorConfig) -> bool {
+        self.monitor_type != other.monitor_type
+            || self.path != other.path
+            || self.args != other.args
+            || self.pattern != other.pattern
+            || self.format != other.format
+            || self.exclude_pattern != other.exclude_pattern
+            || self.rate_limit_burst != other.rate_limit_burst
+            || self.rate_limit_window != other.rate_limit_window
+            || self.follow != other.follow
+            || self.context_lines != other.context_lines
+    }
+}
+
+/// Cross-instance quorum settings: when several `sentrylogmon` processes watch the same
+/// log (e.g. a shared NFS file), they probe each other and elect a leader by rank so only
+/// one instance reports each matched event to Sentry. See the `cluster` module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterConfig {
+    /// This instance's rank; the lowest rank among connected peers becomes leader.
+    pub rank: u32,
+    /// Address to accept peer connections on ("unix:/path" or "tcp:host:port").
+    pub listen: String,
+    /// Peer addresses to dial on startup, in the same "unix:"/"tcp:" grammar.
+    #[serde(default)]
+    pub peers: Vec<String>,
+    /// Sliding window, in seconds, for the leader's event dedup set.
+    #[serde(default = "default_dedup_window_secs")]
+    pub dedup_window_secs: u64,
+}
+
+fn default_dedup_window_secs() -> u64 {
+    30
+}
+
+/// Local sinks a matched event is fanned out to alongside (or instead of) Sentry,
+/// following Pulsar's logger module gaining console/syslog toggles. See `outputs::Sink`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OutputsConfig {
+    /// Emit matched events to stdout.
+    #[serde(default)]
+    pub console: bool,
+    /// Append matched events to this file, in lonk's `log_rules` style.
+    #[serde(default)]
+    pub file: Option<String>,
+    /// Send matched events to a remote syslog target.
+    #[serde(default)]
+    pub syslog: Option<SyslogOutputConfig>,
+}
+
+impl OutputsConfig {
+    /// Whether no local sink is enabled, used to decide whether a Sentry DSN is still
+    /// required.
+    pub fn is_empty(&self) -> bool {
+        !self.console && self.file.is_none() && self.syslog.is_none()
+    }
+}
+
+/// A remote syslog output target, in the same `tcp:`/`udp:` address grammar as
+/// `sources::syslog::SyslogSource` (only `udp:` is actually supported for sending).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyslogOutputConfig {
+    #[serde(default = "default_syslog_facility")]
+    pub facility: String,
+    pub target: String,
+}
+
+fn default_syslog_facility() -> String {
+    "user".to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct FileConfig {
+    /// Schema generation of this file, so future releases can migrate older configs.
+    #[serde(default = "default_version")]
+    pub version: u32,
     #[serde(default)]
     pub sentry: SentryConfig,
     #[serde(default)]
     pub monitors: Vec<MonitorConfig>,
+    /// Redact secrets embedded in matched log line bodies before sending them to Sentry.
+    #[serde(default = "default_true")]
+    pub scrub_messages: bool,
+    /// Cross-instance quorum settings; absent means this instance always reports
+    /// locally with no peer dedup.
+    #[serde(default)]
+    pub cluster: Option<ClusterConfig>,
+    /// Declarative `before_send`-style scrub rules, applied in order to every matched log
+    /// line before it becomes a Sentry event. See `scrub::ScrubRule`.
+    #[serde(default)]
+    pub scrub_rules: Vec<crate::scrub::ScrubRule>,
+    /// Local sinks matched events are fanned out to alongside (or instead of) Sentry.
+    #[serde(default)]
+    pub outputs: OutputsConfig,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+/// Current on-disk config schema version this binary understands. Bump this and add a
+/// migration arm to `migrate_file_config` whenever a release renames or splits a
+/// `FileConfig` field.
+pub(crate) const CURRENT_CONFIG_VERSION: u32 = 1;
+
+/// Migrates `file_config` forward to `CURRENT_CONFIG_VERSION` in-memory, filling in new
+/// defaults and rewriting deprecated keys one version at a time, like lonk's versioned
+/// config loader. Bails with a clear upgrade message if the file declares a version newer
+/// than this binary supports, rather than silently ignoring unknown fields.
+pub(crate) fn migrate_file_config(mut file_config: FileConfig) -> Result<FileConfig> {
+    if file_config.version > CURRENT_CONFIG_VERSION {
+        anyhow::bail!(
+            "config version {} requires a newer sentrylogmon (this binary supports up to version {})",
+            file_config.version,
+            CURRENT_CONFIG_VERSION
+        );
+    }
+
+    // No migrations exist yet: version 1 is both the oldest and current schema. The next
+    // time CURRENT_CONFIG_VERSION is bumped, branch on `file_config.version` here to
+    // rewrite deprecated keys before advancing it, e.g.:
+    //   if file_config.version == 1 { /* rewrite deprecated keys */ }
+    while file_config.version < CURRENT_CONFIG_VERSION {
+        file_config.version += 1;
+    }
+
+    Ok(file_config)
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Resolves `-v`/`-q` occurrence counts and the `SENTRYLOGMON_LOG` environment variable
+/// into an `EnvFilter` directive string and a derived `verbose` bool, in that precedence
+/// order: `SENTRYLOGMON_LOG` wins when set (e.g. `syslog=debug,file=warn` to scope noise
+/// per-module), otherwise `-v`/`-q` map to a single global level (0 -> warn, 1 -> info,
+/// 2 -> debug, 3+ -> trace; each `-q` steps down instead: 1 -> error, 2+ -> off).
+fn resolve_log_filter(verbose: u8, quiet: u8) -> (String, bool) {
+    if let Ok(directive) = std::env::var("SENTRYLOGMON_LOG") {
+        if !directive.is_empty() {
+            return (directive, true);
+        }
+    }
+
+    let level = if quiet > 0 {
+        match quiet {
+            1 => "error",
+            _ => "off",
+        }
+    } else {
+        match verbose {
+            0 => "warn",
+            1 => "info",
+            2 => "debug",
+            _ => "trace",
+        }
+    };
+
+    (level.to_string(), verbose > 0)
 }
 
 #[derive(Parser, Debug)]
@@ -65,17 +276,18 @@ pub struct Args {
     #[arg(long)]
     pub dmesg: bool,
 
-    /// Monitor a log file
+    /// Monitor a log file; repeatable to tail several files at once, alongside any other
+    /// `--dmesg`/`--journalctl`/`--command`/`--syslog` sources given in the same invocation
     #[arg(long)]
-    pub file: Option<PathBuf>,
+    pub file: Vec<PathBuf>,
 
-    /// Monitor journalctl output (pass args)
+    /// Monitor journalctl output (pass args); repeatable
     #[arg(long)]
-    pub journalctl: Option<String>,
+    pub journalctl: Vec<String>,
 
-    /// Monitor custom command output
+    /// Monitor custom command output; repeatable
     #[arg(long)]
-    pub command: Option<String>,
+    pub command: Vec<String>,
 
     /// Monitor syslog (e.g. udp:127.0.0.1:5514)
     #[arg(long)]
@@ -93,6 +305,22 @@ pub struct Args {
     #[arg(long)]
     pub exclude: Option<String>,
 
+    /// Token-bucket burst size for this monitor's rate limiter: up to this many events are
+    /// let through immediately, refilling continuously over `--rate-limit-window`. Only
+    /// used in CLI-only mode; see `MonitorConfig::rate_limit_burst`.
+    #[arg(long = "rate-limit-burst")]
+    pub rate_limit_burst: Option<u32>,
+
+    /// Token-bucket refill window ("30s", "5m", "1h", "250ms"): the burst fully refills
+    /// over this duration. Only used in CLI-only mode; see
+    /// `MonitorConfig::rate_limit_window`.
+    #[arg(long = "rate-limit-window")]
+    pub rate_limit_window: Option<String>,
+
+    /// Keep tailing a monitored file after EOF instead of restarting from the top
+    #[arg(long)]
+    pub follow: bool,
+
     /// Sentry environment
     #[arg(long, default_value = "production")]
     pub environment: String,
@@ -101,14 +329,51 @@ pub struct Args {
     #[arg(long)]
     pub release: Option<String>,
 
-    /// Verbose logging
-    #[arg(short, long)]
-    pub verbose: bool,
+    /// Increase logging verbosity; repeatable (-v info, -vv debug, -vvv trace). Conflicts
+    /// with `--quiet`. `SENTRYLOGMON_LOG` takes precedence over both when set.
+    #[arg(short = 'v', long = "verbose", action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Decrease logging verbosity; repeatable (-q error, -qq off).
+    #[arg(short = 'q', long = "quiet", action = clap::ArgAction::Count)]
+    pub quiet: u8,
 
     /// Run once and exit when input stream ends
     #[arg(long)]
     pub oneshot: bool,
 
+    /// Disable redaction of secrets embedded in matched log line bodies before they're
+    /// sent to Sentry (URL credentials, Authorization headers, password/token/secret/key
+    /// fields, high-entropy hex/base64 runs)
+    #[arg(long)]
+    pub no_scrub_messages: bool,
+
+    /// Path to a YAML rules file of declarative `before_send`-style scrub rules, applied
+    /// in order to every matched log line before it becomes a Sentry event (see
+    /// `scrub::ScrubRule`). Only consulted in CLI-only mode; a config file's own
+    /// `scrub_rules` section is used otherwise.
+    #[arg(long)]
+    pub scrub: Option<PathBuf>,
+
+    /// Emit matched events to stdout as well as (or instead of) Sentry. Only used in
+    /// CLI-only mode; a config file's own `outputs` section is used otherwise.
+    #[arg(long = "output-console")]
+    pub output_console: bool,
+
+    /// Append matched events to this file as well as (or instead of) Sentry. Only used in
+    /// CLI-only mode.
+    #[arg(long = "output-file")]
+    pub output_file: Option<PathBuf>,
+
+    /// Send matched events to a syslog target ("udp:host:port") as well as (or instead of)
+    /// Sentry, reusing `--syslog`'s address grammar. Only used in CLI-only mode.
+    #[arg(long = "output-syslog")]
+    pub output_syslog: Option<String>,
+
+    /// Syslog facility for `--output-syslog` (e.g. "user", "local0").
+    #[arg(long = "output-syslog-facility", default_value = "user")]
+    pub output_syslog_facility: String,
+
     /// List running instances
     #[arg(long)]
     pub status: bool,
@@ -116,40 +381,177 @@ pub struct Args {
     /// Update/Restart all running instances
     #[arg(long)]
     pub update: bool,
+
+    /// Report per-monitor health (last read time, match/exclude/rate-limit counters,
+    /// child-process liveness) for all running instances
+    #[arg(long)]
+    pub health: bool,
+
+    /// Additional named config source to fetch and merge (repeatable), in the
+    /// `file://`/`http://`/`https://` grammar; later sources override same-named
+    /// monitors. When set, takes the place of `--config` as the primary config source.
+    #[arg(long = "config-source")]
+    pub config_sources: Vec<String>,
+
+    /// How often to re-fetch and reconcile from the config source(s) ("30s", "5m").
+    /// Unset disables periodic refresh; the IPC `--update` signal can still trigger one
+    /// manually.
+    #[arg(long = "config-refresh-interval")]
+    pub config_refresh_interval: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub sentry: SentryConfig,
     pub monitors: Vec<MonitorConfig>,
+    /// Resolved `tracing_subscriber::EnvFilter` directive string, used to initialize
+    /// tracing at startup. Either `SENTRYLOGMON_LOG` verbatim, or a single global level
+    /// derived from `-v`/`-q`. See `resolve_log_filter`.
+    pub log_filter: String,
+    /// Whether `Monitor`'s own per-line info logging is enabled: true for `-v` and above,
+    /// and whenever `SENTRYLOGMON_LOG` is set (whatever level it names; the filter then
+    /// decides what is shown). Derived alongside `log_filter` so both come from the
+    /// same `-v`/`-q`/`SENTRYLOGMON_LOG` resolution.
     pub verbose: bool,
     pub oneshot: bool,
+    #[serde(default = "default_true")]
+    pub scrub_messages: bool,
+    /// Declarative `before_send`-style scrub rules, applied in order to every matched log
+    /// line before it becomes a Sentry event. See `scrub::ScrubRule`.
+    #[serde(default)]
+    pub scrub_rules: Vec<crate::scrub::ScrubRule>,
+    /// Local sinks matched events are fanned out to alongside (or instead of) Sentry. At
+    /// least one of this or `sentry.dsn` must be set.
+    #[serde(default)]
+    pub outputs: OutputsConfig,
     #[serde(skip)]
     pub status: bool,
     #[serde(skip)]
     pub update: bool,
+    #[serde(skip)]
+    pub health: bool,
+    /// Path the running config was loaded from, if any, so it can be watched for changes.
+    #[serde(skip)]
+    pub config_path: Option<PathBuf>,
+    /// Cross-instance quorum settings, only available via a config file (see `cluster`).
+    #[serde(default)]
+    pub cluster: Option<ClusterConfig>,
+    /// Named config source URIs this instance was loaded from, if any, so they can be
+    /// periodically re-fetched and reconciled (see `config::source`).
+    #[serde(skip)]
+    pub config_sources: Vec<String>,
+    /// How often to re-fetch `config_sources`; `None` disables periodic refresh.
+    #[serde(skip)]
+    pub config_refresh_interval: Option<Duration>,
 }
 
 impl Config {
-    pub fn load() -> Result<Self> {
+    pub async fn load() -> Result<Self> {
         let args = Args::parse();
-        Self::from_args(args)
+        Self::from_args(args).await
     }
 
-    fn from_args(args: Args) -> Result<Self> {
-        let config = if let Some(config_path) = &args.config {
-            let content = std::fs::read_to_string(config_path)
-                .with_context(|| format!("Failed to read config file: {:?}", config_path))?;
-            let file_config: FileConfig = serde_yaml::from_str(&content)
-                .with_context(|| format!("Failed to parse config file: {:?}", config_path))?;
+    /// Reloads just the monitor list from wherever this running config came from (its
+    /// `config_sources`, if any, via `source_set`, otherwise its single `config_path`),
+    /// for reconciliation driven by the IPC `/update` signal or a periodic background
+    /// refresh. `source_set` should be the same `source::ConfigSourceSet` across every
+    /// call for a given instance, so per-source change-detection state (ETag/Last-
+    /// Modified) survives between refreshes. Errors if this instance has no reloadable
+    /// source (e.g. CLI-only mode).
+    pub async fn reload_monitors(
+        &self,
+        source_set: Option<&source::ConfigSourceSet>,
+    ) -> Result<Vec<MonitorConfig>> {
+        if let Some(source_set) = source_set {
+            Ok(source_set.fetch_and_merge().await?.monitors)
+        } else if let Some(path) = &self.config_path {
+            Ok(Self::from_file(path)?.monitors)
+        } else {
+            anyhow::bail!("this instance has no reloadable config source")
+        }
+    }
+
+    /// Parses a config file, picking the format from its extension. `.toml` files are
+    /// parsed as TOML; anything else (including the historical default) is parsed as YAML.
+    pub fn from_file(path: &Path) -> Result<FileConfig> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {:?}", path))?;
+
+        let file_config: FileConfig = if path.extension().and_then(|e| e.to_str()) == Some("toml")
+        {
+            toml::from_str(&content)
+                .with_context(|| format!("Failed to parse TOML config file: {:?}", path))?
+        } else {
+            serde_yaml::from_str(&content)
+                .with_context(|| format!("Failed to parse config file: {:?}", path))?
+        };
+
+        migrate_file_config(file_config)
+    }
+
+    async fn from_args(args: Args) -> Result<Self> {
+        let refresh_interval = args
+            .config_refresh_interval
+            .as_deref()
+            .map(crate::monitor::parse_window)
+            .filter(|d| !d.is_zero());
+        let (log_filter, verbose) = resolve_log_filter(args.verbose, args.quiet);
+
+        let config = if !args.config_sources.is_empty() {
+            let file_config = source::fetch_and_merge(&args.config_sources).await?;
+
+            let mut cfg = Config {
+                sentry: file_config.sentry,
+                monitors: file_config.monitors,
+                log_filter: log_filter.clone(),
+                verbose,
+                oneshot: args.oneshot,
+                scrub_messages: file_config.scrub_messages,
+                scrub_rules: file_config.scrub_rules,
+                outputs: file_config.outputs,
+                status: args.status,
+                update: args.update,
+                health: args.health,
+                config_path: None,
+                cluster: file_config.cluster,
+                config_sources: args.config_sources.clone(),
+                config_refresh_interval: refresh_interval,
+            };
+
+            // Override with CLI args if provided
+            if let Some(dsn) = &args.dsn {
+                cfg.sentry.dsn = dsn.clone();
+            }
+            if args.environment != "production" {
+                cfg.sentry.environment = args.environment.clone();
+            }
+            if let Some(release) = &args.release {
+                cfg.sentry.release = release.clone();
+            }
+            if args.no_scrub_messages {
+                cfg.scrub_messages = false;
+            }
+
+            cfg
+        } else if let Some(config_path) = &args.config {
+            let file_config = Self::from_file(config_path)?;
 
             let mut cfg = Config {
                 sentry: file_config.sentry,
                 monitors: file_config.monitors,
-                verbose: args.verbose,
+                log_filter: log_filter.clone(),
+                verbose,
                 oneshot: args.oneshot,
+                scrub_messages: file_config.scrub_messages,
+                scrub_rules: file_config.scrub_rules,
+                outputs: file_config.outputs,
                 status: args.status,
                 update: args.update,
+                health: args.health,
+                config_path: Some(config_path.clone()),
+                cluster: file_config.cluster,
+                config_sources: Vec::new(),
+                config_refresh_interval: refresh_interval,
             };
 
             // Override with CLI args if provided
@@ -162,6 +564,9 @@ impl Config {
             if let Some(release) = &args.release {
                 cfg.sentry.release = release.clone();
             }
+            if args.no_scrub_messages {
+                cfg.scrub_messages = false;
+            }
 
             cfg
         } else {
@@ -182,46 +587,62 @@ impl Config {
                         format_arg.clone()
                     },
                     exclude_pattern: args.exclude.clone().unwrap_or_default(),
-                    rate_limit_burst: None,
-                    rate_limit_window: None,
+                    rate_limit_burst: args.rate_limit_burst,
+                    rate_limit_window: args.rate_limit_window.clone(),
+                    follow: false,
+                    context_lines: None,
                 });
-            } else if let Some(file_path) = &args.file {
+            }
+
+            for (i, file_path) in args.file.iter().enumerate() {
                 monitors.push(MonitorConfig {
-                    name: "file".to_string(),
+                    name: source_name("file", i, args.file.len()),
                     monitor_type: "file".to_string(),
                     path: file_path.to_string_lossy().to_string(),
                     args: String::new(),
                     pattern: args.pattern.clone(),
                     format: format_arg.clone(),
                     exclude_pattern: args.exclude.clone().unwrap_or_default(),
-                    rate_limit_burst: None,
-                    rate_limit_window: None,
+                    rate_limit_burst: args.rate_limit_burst,
+                    rate_limit_window: args.rate_limit_window.clone(),
+                    follow: args.follow,
+                    context_lines: None,
                 });
-            } else if let Some(journalctl_args) = &args.journalctl {
+            }
+
+            for (i, journalctl_args) in args.journalctl.iter().enumerate() {
                 monitors.push(MonitorConfig {
-                    name: "journalctl".to_string(),
+                    name: source_name("journalctl", i, args.journalctl.len()),
                     monitor_type: "journalctl".to_string(),
                     path: String::new(),
                     args: journalctl_args.clone(),
                     pattern: args.pattern.clone(),
                     format: format_arg.clone(),
                     exclude_pattern: args.exclude.clone().unwrap_or_default(),
-                    rate_limit_burst: None,
-                    rate_limit_window: None,
+                    rate_limit_burst: args.rate_limit_burst,
+                    rate_limit_window: args.rate_limit_window.clone(),
+                    follow: false,
+                    context_lines: None,
                 });
-            } else if let Some(cmd) = &args.command {
+            }
+
+            for (i, cmd) in args.command.iter().enumerate() {
                 monitors.push(MonitorConfig {
-                    name: "command".to_string(),
+                    name: source_name("command", i, args.command.len()),
                     monitor_type: "command".to_string(),
                     path: String::new(),
                     args: cmd.clone(),
                     pattern: args.pattern.clone(),
                     format: format_arg.clone(),
                     exclude_pattern: args.exclude.clone().unwrap_or_default(),
-                    rate_limit_burst: None,
-                    rate_limit_window: None,
+                    rate_limit_burst: args.rate_limit_burst,
+                    rate_limit_window: args.rate_limit_window.clone(),
+                    follow: false,
+                    context_lines: None,
                 });
-            } else if let Some(syslog_addr) = &args.syslog {
+            }
+
+            if let Some(syslog_addr) = &args.syslog {
                 monitors.push(MonitorConfig {
                     name: "syslog".to_string(),
                     monitor_type: "syslog".to_string(),
@@ -230,11 +651,27 @@ impl Config {
                     pattern: args.pattern.clone(),
                     format: format_arg.clone(),
                     exclude_pattern: args.exclude.clone().unwrap_or_default(),
-                    rate_limit_burst: None,
-                    rate_limit_window: None,
+                    rate_limit_burst: args.rate_limit_burst,
+                    rate_limit_window: args.rate_limit_window.clone(),
+                    follow: false,
+                    context_lines: None,
                 });
             }
 
+            let scrub_rules = match &args.scrub {
+                Some(path) => crate::scrub::load_rules_file(path)?,
+                None => Vec::new(),
+            };
+
+            let outputs = OutputsConfig {
+                console: args.output_console,
+                file: args.output_file.as_ref().map(|p| p.to_string_lossy().to_string()),
+                syslog: args.output_syslog.as_ref().map(|target| SyslogOutputConfig {
+                    facility: args.output_syslog_facility.clone(),
+                    target: target.clone(),
+                }),
+            };
+
             Config {
                 sentry: SentryConfig {
                     dsn: args.dsn.unwrap_or_default(),
@@ -242,25 +679,42 @@ impl Config {
                     release: args.release.unwrap_or_default(),
                 },
                 monitors,
-                verbose: args.verbose,
+                log_filter,
+                verbose,
                 oneshot: args.oneshot,
+                scrub_messages: !args.no_scrub_messages,
+                scrub_rules,
+                outputs,
                 status: args.status,
                 update: args.update,
+                health: args.health,
+                config_path: None,
+                cluster: None,
+                config_sources: Vec::new(),
+                config_refresh_interval: refresh_interval,
             }
         };
 
-        if config.status || config.update {
+        if config.status || config.update || config.health {
             return Ok(config);
         }
 
-        if config.sentry.dsn.is_empty() {
-            anyhow::bail!("Sentry DSN is required. Set via --dsn flag, SENTRY_DSN environment variable, or config file");
+        if config.sentry.dsn.is_empty() && config.outputs.is_empty() {
+            anyhow::bail!(
+                "Sentry DSN is required unless at least one local output sink is configured \
+                 (--output-console, --output-file, --output-syslog, or a config file's \
+                 `outputs` section). Set the DSN via --dsn, SENTRY_DSN, or config file."
+            );
         }
 
         if config.monitors.is_empty() {
             anyhow::bail!("No monitors configured. Use --file, --dmesg, --journalctl, --command, or config file.");
         }
 
+        for mon_cfg in &config.monitors {
+            validate_rate_limit(mon_cfg)?;
+        }
+
         Ok(config)
     }
 }
@@ -269,8 +723,8 @@ impl Config {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_config_from_args_with_format() {
+    #[tokio::test]
+    async fn test_config_from_args_with_format() {
         let args = Args::parse_from(&[
             "sentrylogmon",
             "--file", "/tmp/test.log",
@@ -278,39 +732,39 @@ mod tests {
             "--dsn", "https://example.com"
         ]);
 
-        let config = Config::from_args(args).unwrap();
+        let config = Config::from_args(args).await.unwrap();
         assert_eq!(config.monitors.len(), 1);
         assert_eq!(config.monitors[0].format, "nginx");
     }
 
-    #[test]
-    fn test_config_from_args_without_format() {
+    #[tokio::test]
+    async fn test_config_from_args_without_format() {
         let args = Args::parse_from(&[
             "sentrylogmon",
             "--file", "/tmp/test.log",
             "--dsn", "https://example.com"
         ]);
 
-        let config = Config::from_args(args).unwrap();
+        let config = Config::from_args(args).await.unwrap();
         assert_eq!(config.monitors.len(), 1);
         assert_eq!(config.monitors[0].format, "");
     }
 
-    #[test]
-    fn test_config_from_args_dmesg_default_format() {
+    #[tokio::test]
+    async fn test_config_from_args_dmesg_default_format() {
          let args = Args::parse_from(&[
             "sentrylogmon",
             "--dmesg",
             "--dsn", "https://example.com"
         ]);
 
-        let config = Config::from_args(args).unwrap();
+        let config = Config::from_args(args).await.unwrap();
         assert_eq!(config.monitors.len(), 1);
         assert_eq!(config.monitors[0].format, "dmesg");
     }
 
-    #[test]
-    fn test_config_from_args_dmesg_override_format() {
+    #[tokio::test]
+    async fn test_config_from_args_dmesg_override_format() {
          let args = Args::parse_from(&[
             "sentrylogmon",
             "--dmesg",
@@ -318,8 +772,124 @@ mod tests {
             "--dsn", "https://example.com"
         ]);
 
-        let config = Config::from_args(args).unwrap();
+        let config = Config::from_args(args).await.unwrap();
         assert_eq!(config.monitors.len(), 1);
         assert_eq!(config.monitors[0].format, "custom");
     }
+
+    #[tokio::test]
+    async fn test_config_from_args_combines_multiple_sources() {
+        let args = Args::parse_from(&[
+            "sentrylogmon",
+            "--dmesg",
+            "--file", "/tmp/a.log",
+            "--file", "/tmp/b.log",
+            "--command", "journalctl -f",
+            "--dsn", "https://example.com"
+        ]);
+
+        let config = Config::from_args(args).await.unwrap();
+        let names: Vec<&str> = config.monitors.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["dmesg", "file-1", "file-2", "command"]);
+    }
+
+    #[tokio::test]
+    async fn test_config_from_args_allows_missing_dsn_with_output_sink() {
+        let args = Args::parse_from(&[
+            "sentrylogmon",
+            "--file", "/tmp/test.log",
+            "--output-console",
+        ]);
+
+        let config = Config::from_args(args).await.unwrap();
+        assert!(config.sentry.dsn.is_empty());
+        assert!(config.outputs.console);
+    }
+
+    #[tokio::test]
+    async fn test_config_from_args_rejects_missing_dsn_and_outputs() {
+        let args = Args::parse_from(&[
+            "sentrylogmon",
+            "--file", "/tmp/test.log",
+        ]);
+
+        let err = Config::from_args(args).await.unwrap_err();
+        assert!(err.to_string().contains("Sentry DSN is required"));
+    }
+
+    #[tokio::test]
+    async fn test_config_from_args_rejects_zero_rate_limit_burst() {
+        let args = Args::parse_from(&[
+            "sentrylogmon",
+            "--file", "/tmp/test.log",
+            "--dsn", "https://example.com",
+            "--rate-limit-burst", "0",
+            "--rate-limit-window", "30s",
+        ]);
+
+        let err = Config::from_args(args).await.unwrap_err();
+        assert!(err.to_string().contains("rate_limit_burst must be at least 1"));
+    }
+
+    #[tokio::test]
+    async fn test_config_from_args_rejects_zero_rate_limit_window() {
+        let args = Args::parse_from(&[
+            "sentrylogmon",
+            "--file", "/tmp/test.log",
+            "--dsn", "https://example.com",
+            "--rate-limit-burst", "5",
+            "--rate-limit-window", "0s",
+        ]);
+
+        let err = Config::from_args(args).await.unwrap_err();
+        assert!(err.to_string().contains("must parse to a non-zero duration"));
+    }
+
+    #[tokio::test]
+    async fn test_config_from_args_accepts_valid_rate_limit() {
+        let args = Args::parse_from(&[
+            "sentrylogmon",
+            "--file", "/tmp/test.log",
+            "--dsn", "https://example.com",
+            "--rate-limit-burst", "5",
+            "--rate-limit-window", "250ms",
+        ]);
+
+        let config = Config::from_args(args).await.unwrap();
+        assert_eq!(config.monitors[0].rate_limit_burst, Some(5));
+        assert_eq!(config.monitors[0].rate_limit_window.as_deref(), Some("250ms"));
+    }
+
+    #[test]
+    fn resolve_log_filter_maps_verbosity_counts() {
+        std::env::remove_var("SENTRYLOGMON_LOG");
+        assert_eq!(resolve_log_filter(0, 0), ("warn".to_string(), false));
+        assert_eq!(resolve_log_filter(1, 0), ("info".to_string(), true));
+        assert_eq!(resolve_log_filter(2, 0), ("debug".to_string(), true));
+        assert_eq!(resolve_log_filter(3, 0), ("trace".to_string(), true));
+        assert_eq!(resolve_log_filter(0, 1), ("error".to_string(), false));
+        assert_eq!(resolve_log_filter(0, 2), ("off".to_string(), false));
+    }
+
+    #[test]
+    fn migrate_file_config_accepts_current_version() {
+        let file_config = FileConfig {
+            version: CURRENT_CONFIG_VERSION,
+            ..Default::default()
+        };
+
+        let migrated = migrate_file_config(file_config).unwrap();
+        assert_eq!(migrated.version, CURRENT_CONFIG_VERSION);
+    }
+
+    #[test]
+    fn migrate_file_config_rejects_newer_than_supported() {
+        let file_config = FileConfig {
+            version: CURRENT_CONFIG_VERSION + 1,
+            ..Default::default()
+        };
+
+        let err = migrate_file_config(file_config).unwrap_err();
+        assert!(err.to_string().contains("requires a newer sentrylogmon"));
+    }
 }
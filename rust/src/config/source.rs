@@ -0,0 +1,376 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::{FileConfig, MonitorConfig};
+
+/// A named, fetchable source of monitor configuration, modeled on wgconfd's
+/// periodically-refreshed named sources: `Config::load` resolves an ordered list of
+/// these and merges their monitor lists, later sources overriding same-named monitors.
+#[async_trait]
+pub trait ConfigSource: Send + Sync {
+    /// A short identifier for logging (e.g. the URI this source was built from).
+    fn describe(&self) -> &str;
+
+    /// Fetches the current config from this source. Returns `Ok(None)` when the source
+    /// supports change detection (ETag/Last-Modified) and the content hasn't changed
+    /// since the last successful fetch.
+    async fn fetch(&mut self) -> Result<Option<FileConfig>>;
+}
+
+/// Reads a local file each time it's fetched; relies on `ConfigWatcher` or the periodic
+/// refresh loop for change detection rather than doing its own.
+pub struct FileConfigSource {
+    path: PathBuf,
+}
+
+impl FileConfigSource {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait]
+impl ConfigSource for FileConfigSource {
+    fn describe(&self) -> &str {
+        self.path.to_str().unwrap_or("<file>")
+    }
+
+    async fn fetch(&mut self) -> Result<Option<FileConfig>> {
+        super::Config::from_file(&self.path).map(Some)
+    }
+}
+
+/// Fetches config from an HTTP(S) endpoint, skipping the rebuild when the upstream
+/// reports via `ETag`/`Last-Modified` that nothing has changed since the last fetch.
+pub struct HttpConfigSource {
+    url: String,
+    client: reqwest::Client,
+    last_etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl HttpConfigSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::new(),
+            last_etag: None,
+            last_modified: None,
+        }
+    }
+}
+
+#[async_trait]
+impl ConfigSource for HttpConfigSource {
+    fn describe(&self) -> &str {
+        &self.url
+    }
+
+    async fn fetch(&mut self) -> Result<Option<FileConfig>> {
+        let mut req = self.client.get(&self.url);
+        if let Some(etag) = &self.last_etag {
+            req = req.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            req = req.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let resp = req
+            .send()
+            .await
+            .with_context(|| format!("failed to fetch config from {}", self.url))?;
+
+        if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+
+        let resp = resp
+            .error_for_status()
+            .with_context(|| format!("config fetch from {} returned an error status", self.url))?;
+
+        self.last_etag = resp
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        self.last_modified = resp
+            .headers()
+            .get(reqwest::header::LAST_MODIFIED)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+
+        let body = resp
+            .text()
+            .await
+            .with_context(|| format!("failed to read config body from {}", self.url))?;
+
+        let file_config: FileConfig = serde_yaml::from_str(&body)
+            .with_context(|| format!("failed to parse config fetched from {}", self.url))?;
+
+        Ok(Some(super::migrate_file_config(file_config)?))
+    }
+}
+
+/// A config supplied verbatim at construction time (e.g. inline YAML from a CLI flag),
+/// always returned as-is.
+pub struct InlineConfigSource {
+    label: String,
+    config: FileConfig,
+}
+
+impl InlineConfigSource {
+    pub fn new(label: impl Into<String>, config: FileConfig) -> Self {
+        Self {
+            label: label.into(),
+            config,
+        }
+    }
+}
+
+#[async_trait]
+impl ConfigSource for InlineConfigSource {
+    fn describe(&self) -> &str {
+        &self.label
+    }
+
+    async fn fetch(&mut self) -> Result<Option<FileConfig>> {
+        Ok(Some(self.config.clone()))
+    }
+}
+
+/// Builds a `ConfigSource` from a URI, following the `file://`/`http://`/`https://`
+/// grammar (compare `sources::syslog`'s `tcp:`/`udp:`/`unix:` address prefixes).
+pub fn from_uri(uri: &str) -> Result<Box<dyn ConfigSource>> {
+    if let Some(path) = uri.strip_prefix("file://") {
+        Ok(Box::new(FileConfigSource::new(PathBuf::from(path))))
+    } else if uri.starts_with("http://") || uri.starts_with("https://") {
+        Ok(Box::new(HttpConfigSource::new(uri.to_string())))
+    } else {
+        anyhow::bail!("unsupported config source URI: {}", uri)
+    }
+}
+
+/// Fetches every source in order and merges the results: later sources override
+/// same-named monitors, and scalar sections (`sentry`, `scrub_messages`, `cluster`) are
+/// taken from the last source that set them. Builds a fresh `ConfigSource` per URI, so
+/// this is only appropriate for a one-off fetch (the initial `Config::from_args` load);
+/// repeated refreshes should go through `ConfigSourceSet` instead so each source's
+/// ETag/Last-Modified state survives across calls.
+pub async fn fetch_and_merge(uris: &[String]) -> Result<FileConfig> {
+    let mut fetched = Vec::with_capacity(uris.len());
+
+    for uri in uris {
+        let mut source = from_uri(uri)?;
+        match source.fetch().await? {
+            Some(file_config) => fetched.push(file_config),
+            None => anyhow::bail!(
+                "config source {} returned no content on first fetch",
+                source.describe()
+            ),
+        }
+    }
+
+    Ok(merge(fetched))
+}
+
+struct SourceState {
+    source: Box<dyn ConfigSource>,
+    last: Option<FileConfig>,
+}
+
+/// An ordered set of `ConfigSource`s built once (from `--config-source` URIs) and reused
+/// across every subsequent refresh, so a source like `HttpConfigSource` keeps the
+/// ETag/Last-Modified state it needs for its `NOT_MODIFIED` short-circuit to ever fire.
+/// Built once in `main()` and shared by clone between the periodic background refresh and
+/// the IPC `/update` signal, the same way `sysstat::Collector`/`scrub::ScrubSet`/
+/// `cluster::ClusterHandle` are, and passed into `Config::reload_monitors` on every call.
+pub struct ConfigSourceSet {
+    state: tokio::sync::Mutex<Vec<SourceState>>,
+}
+
+impl ConfigSourceSet {
+    pub fn new(uris: &[String]) -> Result<Self> {
+        let sources = uris
+            .iter()
+            .map(|uri| from_uri(uri))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::from_sources(sources))
+    }
+
+    fn from_sources(sources: Vec<Box<dyn ConfigSource>>) -> Self {
+        let state = sources
+            .into_iter()
+            .map(|source| SourceState { source, last: None })
+            .collect();
+        Self {
+            state: tokio::sync::Mutex::new(state),
+        }
+    }
+
+    /// Fetches every source in order and merges the results, same semantics as
+    /// `fetch_and_merge`, but reusing each source's change-detection state: a source that
+    /// reports `Ok(None)` (nothing changed since last time) contributes its last-fetched
+    /// `FileConfig` instead of being treated as empty.
+    pub async fn fetch_and_merge(&self) -> Result<FileConfig> {
+        let mut state = self.state.lock().await;
+        let mut fetched = Vec::with_capacity(state.len());
+
+        for entry in state.iter_mut() {
+            match entry.source.fetch().await? {
+                Some(file_config) => {
+                    entry.last = Some(file_config.clone());
+                    fetched.push(file_config);
+                }
+                None => match &entry.last {
+                    Some(cached) => fetched.push(cached.clone()),
+                    None => anyhow::bail!(
+                        "config source {} returned no content on first fetch",
+                        entry.source.describe()
+                    ),
+                },
+            }
+        }
+
+        Ok(merge(fetched))
+    }
+}
+
+/// Merges an ordered list of fetched `FileConfig`s into one, with later sources
+/// overriding same-named monitors and winning ties on scalar fields.
+pub fn merge(configs: Vec<FileConfig>) -> FileConfig {
+    let mut merged = FileConfig::default();
+    let mut monitor_order: Vec<String> = Vec::new();
+    let mut monitors_by_name: HashMap<String, MonitorConfig> = HashMap::new();
+
+    for cfg in configs {
+        merged.version = cfg.version;
+        if !cfg.sentry.dsn.is_empty() {
+            merged.sentry = cfg.sentry;
+        }
+        merged.scrub_messages = cfg.scrub_messages;
+        if cfg.cluster.is_some() {
+            merged.cluster = cfg.cluster;
+        }
+        if !cfg.scrub_rules.is_empty() {
+            merged.scrub_rules = cfg.scrub_rules;
+        }
+        if !cfg.outputs.is_empty() {
+            merged.outputs = cfg.outputs;
+        }
+
+        for mon_cfg in cfg.monitors {
+            if !monitors_by_name.contains_key(&mon_cfg.name) {
+                monitor_order.push(mon_cfg.name.clone());
+            }
+            monitors_by_name.insert(mon_cfg.name.clone(), mon_cfg);
+        }
+    }
+
+    merged.monitors = monitor_order
+        .into_iter()
+        .filter_map(|name| monitors_by_name.remove(&name))
+        .collect();
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::SentryConfig;
+
+    fn mon(name: &str, pattern: &str) -> MonitorConfig {
+        MonitorConfig {
+            name: name.to_string(),
+            monitor_type: "file".to_string(),
+            path: String::new(),
+            args: String::new(),
+            pattern: pattern.to_string(),
+            format: String::new(),
+            exclude_pattern: String::new(),
+            rate_limit_burst: None,
+            rate_limit_window: None,
+            follow: false,
+            context_lines: None,
+        }
+    }
+
+    #[test]
+    fn merge_overrides_same_named_monitors_and_preserves_order() {
+        let first = FileConfig {
+            monitors: vec![mon("a", "Error"), mon("b", "Error")],
+            ..Default::default()
+        };
+        let second = FileConfig {
+            monitors: vec![mon("b", "Fatal"), mon("c", "Error")],
+            ..Default::default()
+        };
+
+        let merged = merge(vec![first, second]);
+
+        let names: Vec<&str> = merged.monitors.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+        assert_eq!(merged.monitors[1].pattern, "Fatal");
+    }
+
+    /// A `ConfigSource` that returns `Some` on its first fetch and `None` (unchanged)
+    /// thereafter, standing in for `HttpConfigSource`'s `NOT_MODIFIED` short-circuit
+    /// without needing a real HTTP server.
+    struct OnceThenUnchangedSource {
+        config: FileConfig,
+        fetched: bool,
+    }
+
+    #[async_trait]
+    impl ConfigSource for OnceThenUnchangedSource {
+        fn describe(&self) -> &str {
+            "once-then-unchanged"
+        }
+
+        async fn fetch(&mut self) -> Result<Option<FileConfig>> {
+            if self.fetched {
+                Ok(None)
+            } else {
+                self.fetched = true;
+                Ok(Some(self.config.clone()))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn config_source_set_reuses_last_config_when_unchanged() {
+        let source = OnceThenUnchangedSource {
+            config: FileConfig {
+                monitors: vec![mon("a", "Error")],
+                ..Default::default()
+            },
+            fetched: false,
+        };
+        let set = ConfigSourceSet::from_sources(vec![Box::new(source)]);
+
+        let first = set.fetch_and_merge().await.unwrap();
+        assert_eq!(first.monitors.len(), 1);
+
+        let second = set.fetch_and_merge().await.unwrap();
+        assert_eq!(second.monitors.len(), 1);
+        assert_eq!(second.monitors[0].name, "a");
+    }
+
+    #[test]
+    fn merge_keeps_last_nonempty_sentry_dsn() {
+        let first = FileConfig {
+            sentry: SentryConfig {
+                dsn: "https://first".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let second = FileConfig::default();
+
+        let merged = merge(vec![first, second]);
+
+        assert_eq!(merged.sentry.dsn, "https://first");
+    }
+}
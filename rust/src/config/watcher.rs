@@ -0,0 +1,69 @@
+use anyhow::Result;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::PathBuf;
+use tokio::sync::mpsc;
+
+use crate::monitor::MonitorHandleRegistry;
+
+use super::Config;
+
+/// Watches the running config file on disk and, whenever it changes, re-parses it and
+/// pushes the new detector/exclusion/rate-limit settings into the matching running
+/// monitors without restarting their log streams. The previous settings are kept if the
+/// new file fails to parse. `monitors` is read afresh from the shared registry on every
+/// reload (rather than a snapshot taken at startup) so a crash-restart or reconcile
+/// respawn that replaces a monitor's `MonitorHandle` is picked up automatically.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    monitors: MonitorHandleRegistry,
+}
+
+impl ConfigWatcher {
+    pub fn new(path: PathBuf, monitors: MonitorHandleRegistry) -> Self {
+        Self { path, monitors }
+    }
+
+    pub async fn watch(self) -> Result<()> {
+        let (tx, mut rx) = mpsc::channel(16);
+        let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
+            let _ = tx.blocking_send(res);
+        })?;
+        watcher.watch(&self.path, RecursiveMode::NonRecursive)?;
+
+        while let Some(res) = rx.recv().await {
+            let event = match res {
+                Ok(event) => event,
+                Err(e) => {
+                    tracing::warn!("Config watcher error: {}", e);
+                    continue;
+                }
+            };
+
+            if !event.kind.is_modify() && !event.kind.is_create() {
+                continue;
+            }
+
+            match Config::from_file(&self.path) {
+                Ok(file_config) => self.apply(&file_config).await,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to reload config from {:?}, keeping previous settings: {}",
+                        self.path,
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn apply(&self, file_config: &super::FileConfig) {
+        for mon_cfg in &file_config.monitors {
+            let handle = self.monitors.lock().unwrap().get(&mon_cfg.name).cloned();
+            if let Some(handle) = handle {
+                handle.reload(mon_cfg).await;
+            }
+        }
+    }
+}
@@ -1,15 +1,27 @@
 use crate::config::Config;
+use crate::monitor::{HealthRegistry, MonitorHealth};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::future::Future;
 use std::os::unix::fs::MetadataExt;
 use std::os::unix::fs::PermissionsExt;
 use std::os::unix::process::CommandExt;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::process::Command;
+use std::sync::Arc;
 use std::time::SystemTime;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::UnixListener;
+use tokio::sync::Mutex;
+
+/// Invoked for a `POST /update` against a process with a reloadable config source:
+/// reloads it and reconciles the live monitor set against it (see
+/// `manager::MonitorManager::reconcile`). `None` when the process has no reloadable
+/// source (CLI-only mode), in which case `/update` falls back to re-exec'ing the process
+/// with its original arguments.
+pub type ReconcileFn = Arc<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct StatusResponse {
@@ -19,6 +31,53 @@ pub struct StatusResponse {
     pub config: Option<Config>,
 }
 
+/// Aggregate or per-monitor health, modeled on Ceph's `OK`/`WARN`/`ERR` cluster health
+/// summary: `Failed` if the underlying process died, `Degraded` for a monitor that's
+/// running but dropping events, `Ok` otherwise.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+    Failed,
+}
+
+impl std::fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            HealthStatus::Ok => "OK",
+            HealthStatus::Degraded => "DEGRADED",
+            HealthStatus::Failed => "FAILED",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+fn monitor_status(health: &MonitorHealth) -> HealthStatus {
+    if health.process_alive == Some(false) {
+        HealthStatus::Failed
+    } else if health.rate_limit_drops > 0 {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Ok
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MonitorHealthRecord {
+    pub name: String,
+    #[serde(flatten)]
+    pub health: MonitorHealth,
+    pub status: HealthStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HealthResponse {
+    pub pid: u32,
+    pub status: HealthStatus,
+    pub monitors: Vec<MonitorHealthRecord>,
+}
+
 pub fn ensure_secure_directory(path: &Path) -> Result<()> {
     if !path.exists() {
         fs::create_dir_all(path)
@@ -63,6 +122,8 @@ pub async fn start_server(
     socket_path: PathBuf,
     config: Config,
     start_time: SystemTime,
+    reconcile: Option<ReconcileFn>,
+    monitor_health: HealthRegistry,
 ) -> Result<()> {
     if socket_path.exists() {
         fs::remove_file(&socket_path).ok();
@@ -74,13 +135,15 @@ pub async fn start_server(
     // Set socket permissions to 0600
     fs::set_permissions(&socket_path, fs::Permissions::from_mode(0o600))?;
 
-    let config = std::sync::Arc::new(config);
-    let socket_path = std::sync::Arc::new(socket_path);
+    let config = Arc::new(config);
+    let socket_path = Arc::new(socket_path);
 
     loop {
         let (mut socket, _) = listener.accept().await?;
         let config = config.clone();
         let socket_path = socket_path.clone();
+        let reconcile = reconcile.clone();
+        let monitor_health = monitor_health.clone();
 
         tokio::spawn(async move {
             let mut buf = [0; 1024];
@@ -102,6 +165,43 @@ pub async fn start_server(
                     config: Some((*config).clone()),
                 };
 
+                if let Ok(json) = serde_json::to_string(&response) {
+                    let resp = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                        json.len(),
+                        json
+                    );
+                    let _ = socket.write_all(resp.as_bytes()).await;
+                }
+            } else if method == "GET" && path == "/health" {
+                let entries: Vec<(String, Arc<Mutex<MonitorHealth>>)> = {
+                    let registry = monitor_health.lock().unwrap();
+                    registry.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+                };
+
+                let mut monitors = Vec::with_capacity(entries.len());
+                for (name, health) in entries {
+                    let snapshot = health.lock().await.clone();
+                    monitors.push(MonitorHealthRecord {
+                        name,
+                        status: monitor_status(&snapshot),
+                        health: snapshot,
+                    });
+                }
+                monitors.sort_by(|a, b| a.name.cmp(&b.name));
+
+                let status = monitors
+                    .iter()
+                    .map(|m| m.status)
+                    .max()
+                    .unwrap_or(HealthStatus::Ok);
+
+                let response = HealthResponse {
+                    pid: std::process::id(),
+                    status,
+                    monitors,
+                };
+
                 if let Ok(json) = serde_json::to_string(&response) {
                     let resp = format!(
                         "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
@@ -111,22 +211,31 @@ pub async fn start_server(
                     let _ = socket.write_all(resp.as_bytes()).await;
                 }
             } else if method == "POST" && path == "/update" {
-                let resp = "HTTP/1.1 200 OK\r\n\r\nRestarting...";
-                let _ = socket.write_all(resp.as_bytes()).await;
-                // Give some time for response to flush
-                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                if let Some(reconcile) = &reconcile {
+                    let resp = "HTTP/1.1 200 OK\r\n\r\nReconciling monitors...";
+                    let _ = socket.write_all(resp.as_bytes()).await;
+                    reconcile().await;
+                } else {
+                    // No reloadable config source (CLI-only mode): fall back to
+                    // restarting the whole process with its original arguments.
+                    let resp = "HTTP/1.1 200 OK\r\n\r\nRestarting...";
+                    let _ = socket.write_all(resp.as_bytes()).await;
+                    // Give some time for response to flush
+                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
-                tracing::info!("Restarting process...");
-                // Remove socket file
-                let _ = fs::remove_file(&*socket_path);
+                    tracing::info!("Restarting process...");
+                    // Remove socket file
+                    let _ = fs::remove_file(&*socket_path);
 
-                let args: Vec<String> = std::env::args().collect();
-                let exe = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("sentrylogmon"));
+                    let args: Vec<String> = std::env::args().collect();
+                    let exe =
+                        std::env::current_exe().unwrap_or_else(|_| PathBuf::from("sentrylogmon"));
 
-                let err = Command::new(exe).args(&args[1..]).exec();
+                    let err = Command::new(exe).args(&args[1..]).exec();
 
-                tracing::error!("Failed to restart: {}", err);
-                std::process::exit(1);
+                    tracing::error!("Failed to restart: {}", err);
+                    std::process::exit(1);
+                }
             } else {
                 let resp = "HTTP/1.1 404 Not Found\r\n\r\n";
                 let _ = socket.write_all(resp.as_bytes()).await;
@@ -180,6 +289,22 @@ pub fn request_update(socket_path: &Path) -> Result<()> {
     Ok(())
 }
 
+pub fn request_health(socket_path: &Path) -> Result<HealthResponse> {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(socket_path)?;
+    stream.write_all(b"GET /health HTTP/1.1\r\n\r\n")?;
+    let mut resp = String::new();
+    stream.read_to_string(&mut resp)?;
+
+    let body_start = resp
+        .find("\r\n\r\n")
+        .ok_or_else(|| anyhow::anyhow!("malformed health response"))?;
+    let body = &resp[body_start + 4..];
+    serde_json::from_str(body).context("failed to parse health response")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
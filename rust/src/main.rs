@@ -1,25 +1,32 @@
+mod cluster;
 mod config;
 mod detectors;
 mod ipc;
+mod manager;
 mod metrics;
 mod monitor;
+mod outputs;
+mod scrub;
 mod sources;
 mod sysstat;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::prelude::*;
 use std::cmp::max;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::SystemTime;
+use tokio::sync::Mutex;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt::init();
+    // Load configuration first so its resolved log filter (SENTRYLOGMON_LOG, falling
+    // back to -v/-q) is known before tracing is initialized.
+    let cfg = config::Config::load().await?;
 
-    // Load configuration
-    let cfg = config::Config::load()?;
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::new(&cfg.log_filter))
+        .init();
 
     if cfg.status {
         let socket_dir = PathBuf::from("/tmp/sentrylogmon");
@@ -33,6 +40,27 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    if cfg.health {
+        let socket_dir = PathBuf::from("/tmp/sentrylogmon");
+        let instances = ipc::list_instances(&socket_dir)?;
+
+        let mut healths = Vec::with_capacity(instances.len());
+        for inst in instances {
+            let socket_path = socket_dir.join(format!("sentrylogmon.{}.sock", inst.pid));
+            match ipc::request_health(&socket_path) {
+                Ok(health) => healths.push(health),
+                Err(e) => println!("Failed to get health for PID {}: {}", inst.pid, e),
+            }
+        }
+
+        if is_terminal() {
+            print!("{}", format_health_table(&healths));
+        } else {
+            println!("{}", serde_json::to_string_pretty(&healths)?);
+        }
+        return Ok(());
+    }
+
     if cfg.update {
         let socket_dir = PathBuf::from("/tmp/sentrylogmon");
         let instances = ipc::list_instances(&socket_dir)?;
@@ -48,31 +76,46 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
-    if cfg.sentry.dsn.is_empty() {
-        anyhow::bail!("Sentry DSN is required");
+    if cfg.sentry.dsn.is_empty() && cfg.outputs.is_empty() {
+        anyhow::bail!(
+            "Sentry DSN is required unless at least one local output sink is configured"
+        );
     }
 
-    // Initialize Sentry
-    let _guard = sentry::init((
-        cfg.sentry.dsn.clone(),
-        sentry::ClientOptions {
-            release: if !cfg.sentry.release.is_empty() {
-                Some(cfg.sentry.release.clone().into())
-            } else {
-                None
+    // Initialize Sentry, unless running as a local-only matcher/forwarder with no DSN
+    // configured (see `cfg.outputs`). `sentry::capture_message` is a no-op without an
+    // initialized client, so the monitors below don't need to know either way.
+    let _guard = if !cfg.sentry.dsn.is_empty() {
+        let guard = sentry::init((
+            cfg.sentry.dsn.clone(),
+            sentry::ClientOptions {
+                release: if !cfg.sentry.release.is_empty() {
+                    Some(cfg.sentry.release.clone().into())
+                } else {
+                    None
+                },
+                environment: Some(cfg.sentry.environment.clone().into()),
+                ..Default::default()
             },
-            environment: Some(cfg.sentry.environment.clone().into()),
-            ..Default::default()
-        },
-    ));
+        ));
 
-    if cfg.verbose {
-        tracing::info!(
-            "Initialized Sentry (env={}, release={})",
-            cfg.sentry.environment,
-            cfg.sentry.release
-        );
-    }
+        if cfg.verbose {
+            tracing::info!(
+                "Initialized Sentry (env={}, release={})",
+                cfg.sentry.environment,
+                cfg.sentry.release
+            );
+        }
+
+        Some(guard)
+    } else {
+        None
+    };
+
+    // Build the local output sinks (console/file/syslog), if any, so matched events can
+    // be fanned out to them even where Sentry isn't reachable.
+    let sinks: Arc<Vec<Arc<dyn outputs::Sink>>> =
+        Arc::new(outputs::build_sinks(&cfg.outputs).await.context("failed to build output sinks")?);
 
     if cfg.monitors.is_empty() {
         anyhow::bail!("No monitors configured");
@@ -82,21 +125,6 @@ async fn main() -> Result<()> {
     let collector = Arc::new(sysstat::Collector::new());
     collector.run().await;
 
-    // Start IPC server
-    let socket_dir = PathBuf::from("/tmp/sentrylogmon");
-    if let Err(e) = ipc::ensure_secure_directory(&socket_dir) {
-        tracing::error!("Failed to ensure secure IPC directory: {}", e);
-    } else {
-        let socket_path = socket_dir.join(format!("sentrylogmon.{}.sock", std::process::id()));
-
-        let cfg_clone = cfg.clone();
-        tokio::spawn(async move {
-            if let Err(e) = ipc::start_server(socket_path, cfg_clone, SystemTime::now()).await {
-                tracing::error!("IPC Server error: {}", e);
-            }
-        });
-    }
-
     // Start Metrics Server
     if cfg.metrics_port > 0 {
         let port = cfg.metrics_port;
@@ -107,101 +135,325 @@ async fn main() -> Result<()> {
         });
     }
 
-    // Start monitors
-    let mut handles = Vec::new();
-
-    for mon_cfg in cfg.monitors.iter() {
-        let source: Box<dyn sources::LogSource> = match mon_cfg.monitor_type.as_str() {
-            "file" => {
-                if mon_cfg.path.is_empty() {
-                    tracing::warn!("Skipping file monitor '{}': path is empty", mon_cfg.name);
-                    continue;
-                }
-                Box::new(sources::file::FileSource::new(
-                    mon_cfg.name.clone(),
-                    PathBuf::from(&mon_cfg.path),
-                ))
-            }
-            "journalctl" => Box::new(sources::journalctl::JournalctlSource::new(
-                mon_cfg.name.clone(),
-                &mon_cfg.args,
-            )),
-            "dmesg" => Box::new(sources::dmesg::DmesgSource::new(mon_cfg.name.clone())),
-            "command" => {
-                let parts: Vec<&str> = mon_cfg.args.split_whitespace().collect();
-                if parts.is_empty() {
-                    tracing::warn!(
-                        "Skipping command monitor '{}': command is empty",
-                        mon_cfg.name
-                    );
-                    continue;
-                }
-                let cmd = parts[0].to_string();
-                let args: Vec<String> = parts[1..].iter().map(|s| s.to_string()).collect();
-                Box::new(sources::command::CommandSource::new(
-                    mon_cfg.name.clone(),
-                    cmd,
-                    args,
-                ))
-            }
-            "syslog" => Box::new(sources::syslog::SyslogSource::new(
-                mon_cfg.name.clone(),
-                mon_cfg.path.clone(),
-            )),
-            _ => {
-                tracing::warn!("Unknown monitor type: {}", mon_cfg.monitor_type);
-                continue;
-            }
-        };
-
-        let detector_format = determine_detector_format(mon_cfg);
-        let detector = match detectors::get_detector(&detector_format, &mon_cfg.pattern) {
-            Ok(d) => d,
+    // Start the cross-instance quorum subsystem, if configured, so matched events can be
+    // deduplicated across sentrylogmon processes watching the same log.
+    let cluster = if let Some(cluster_cfg) = cfg.cluster.clone() {
+        match cluster::ClusterHandle::start(cluster_cfg).await {
+            Ok(handle) => Some(Arc::new(handle)),
             Err(e) => {
-                tracing::error!(
-                    "Failed to create detector for monitor '{}': {}",
-                    mon_cfg.name,
-                    e
-                );
-                continue;
+                tracing::error!("Failed to start cluster subsystem: {}", e);
+                None
             }
-        };
+        }
+    } else {
+        None
+    };
 
-        let mut monitor = monitor::Monitor::new(
-            source,
-            detector,
+    // Compile the declarative scrub rules once at startup so the monitor's hot path
+    // never re-parses a pattern.
+    let scrub_rules = Arc::new(
+        scrub::ScrubSet::compile(&cfg.scrub_rules).context("failed to compile scrub rules")?,
+    );
+
+    // Start monitors, supervised by a MonitorManager that restarts any that crash and
+    // can be reconciled against a freshly-reloaded config via the IPC `/update` signal.
+    // `health_registry` publishes each monitor's liveness/error counters for the IPC
+    // `/health` command; it's looked up (not rebuilt) on every crash-restart or reconcile
+    // respawn so the handle the IPC server reads never goes stale.
+    let manager = manager::MonitorManager::new();
+    let health_registry: monitor::HealthRegistry = Arc::new(std::sync::Mutex::new(std::collections::HashMap::new()));
+    let build_factory = make_build_factory(
+        collector.clone(),
+        cfg.verbose,
+        cfg.oneshot,
+        cfg.scrub_messages,
+        scrub_rules.clone(),
+        sinks.clone(),
+        cluster.clone(),
+        health_registry.clone(),
+    );
+
+    let mut any_monitor = false;
+
+    for mon_cfg in cfg.monitors.iter() {
+        let health = health_handle(&health_registry, &mon_cfg.name);
+        if let Err(e) = build_monitor(
+            mon_cfg,
             collector.clone(),
             cfg.verbose,
             cfg.oneshot,
-            Some(mon_cfg.exclude_pattern.clone()),
-            mon_cfg.rate_limit_burst,
-            mon_cfg.rate_limit_window.clone(),
-        );
+            cfg.scrub_messages,
+            scrub_rules.clone(),
+            sinks.clone(),
+            cluster.clone(),
+            health,
+        ) {
+            tracing::warn!("Skipping monitor '{}': {}", mon_cfg.name, e);
+            continue;
+        }
 
-        let handle = tokio::spawn(async move {
-            if let Err(e) = monitor.start().await {
-                tracing::error!("Monitor error: {}", e);
-            }
-            if let Err(e) = monitor.close().await {
-                tracing::error!("Error closing monitor: {}", e);
-            }
-        });
+        any_monitor = true;
 
-        handles.push(handle);
+        let factory = build_factory(mon_cfg);
+        manager.add(mon_cfg.clone(), move || factory()).await;
     }
 
-    if handles.is_empty() {
+    if !any_monitor {
         anyhow::bail!("No valid monitors to start");
     }
 
-    // Wait for all monitors
-    for handle in handles {
-        let _ = handle.await;
+    // Watch the config file (if any) and hot-swap detector/exclusion/rate-limit settings
+    // into the running monitors without restarting their log streams. The watcher reads
+    // through `manager`'s handle registry, which `MonitorManager::supervise` keeps pointed
+    // at whichever `Monitor` instance is actually running for each name, surviving
+    // crash-restarts and reconcile respawns.
+    if let Some(config_path) = cfg.config_path.clone() {
+        let watcher = config::ConfigWatcher::new(config_path, manager.handles());
+        tokio::spawn(async move {
+            if let Err(e) = watcher.watch().await {
+                tracing::error!("Config watcher error: {}", e);
+            }
+        });
     }
 
+    // Built once and shared across every refresh (periodic and IPC `/update` alike) so
+    // each `--config-source` URI's ETag/Last-Modified state survives between fetches,
+    // instead of being thrown away and rebuilt from scratch on every reload.
+    let config_source_set = if !cfg.config_sources.is_empty() {
+        Some(Arc::new(config::source::ConfigSourceSet::new(
+            &cfg.config_sources,
+        )?))
+    } else {
+        None
+    };
+
+    // Reloads the monitor list from wherever this instance's config came from (a config
+    // file or one or more `--config-source` URIs) and reconciles it into the running
+    // monitor set. Shared by the IPC `/update` signal and the periodic background
+    // refresh below; `None` when this instance has no reloadable source (CLI-only
+    // mode), in which case `/update` falls back to the historical re-exec behavior.
+    let has_reloadable_source = cfg.config_path.is_some() || !cfg.config_sources.is_empty();
+    let reconcile: Option<ipc::ReconcileFn> = has_reloadable_source.then(|| {
+        let cfg = cfg.clone();
+        let manager = manager.clone();
+        let build_factory = build_factory.clone();
+        let config_source_set = config_source_set.clone();
+        Arc::new(move || {
+            let cfg = cfg.clone();
+            let manager = manager.clone();
+            let build_factory = build_factory.clone();
+            let config_source_set = config_source_set.clone();
+            Box::pin(async move {
+                match cfg.reload_monitors(config_source_set.as_deref()).await {
+                    Ok(new_monitors) => {
+                        manager
+                            .reconcile(&new_monitors, |mon_cfg| build_factory(mon_cfg))
+                            .await;
+                    }
+                    Err(e) => {
+                        tracing::warn!("Config reload failed, keeping running monitors: {}", e);
+                    }
+                }
+            }) as std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>>
+        }) as ipc::ReconcileFn
+    });
+
+    // Periodically re-fetch and reconcile from `--config-source` (or the config file),
+    // independent of the manually-triggered IPC `/update` signal.
+    if let (Some(reconcile), Some(interval)) = (&reconcile, cfg.config_refresh_interval) {
+        let reconcile = reconcile.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await; // first tick fires immediately; skip it
+            loop {
+                ticker.tick().await;
+                reconcile().await;
+            }
+        });
+    }
+
+    // Start IPC server
+    let socket_dir = PathBuf::from("/tmp/sentrylogmon");
+    if let Err(e) = ipc::ensure_secure_directory(&socket_dir) {
+        tracing::error!("Failed to ensure secure IPC directory: {}", e);
+    } else {
+        let socket_path = socket_dir.join(format!("sentrylogmon.{}.sock", std::process::id()));
+
+        let cfg_clone = cfg.clone();
+        let health_registry = health_registry.clone();
+        tokio::spawn(async move {
+            if let Err(e) = ipc::start_server(
+                socket_path,
+                cfg_clone,
+                SystemTime::now(),
+                reconcile,
+                health_registry,
+            )
+            .await
+            {
+                tracing::error!("IPC Server error: {}", e);
+            }
+        });
+    }
+
+    manager.run_all().await;
+
     Ok(())
 }
 
+/// Looks up this monitor's health handle in `registry`, creating one on first sight. Used
+/// so every rebuild of a given monitor (crash-restart, reconcile respawn, or the initial
+/// one-off build used to validate each configured monitor) keeps publishing to the same counters
+/// instead of starting a fresh, unread one each time.
+fn health_handle(registry: &monitor::HealthRegistry, name: &str) -> Arc<Mutex<monitor::MonitorHealth>> {
+    registry
+        .lock()
+        .unwrap()
+        .entry(name.to_string())
+        .or_insert_with(|| Arc::new(Mutex::new(monitor::MonitorHealth::default())))
+        .clone()
+}
+
+/// Builds a closure that, given a `MonitorConfig`, returns a factory producing fresh
+/// `Monitor`s for it. Used both for the initial monitor set and to respawn/rebuild
+/// monitors at runtime via `manager::MonitorManager::reconcile`.
+fn make_build_factory(
+    collector: Arc<sysstat::Collector>,
+    verbose: bool,
+    oneshot: bool,
+    scrub_messages: bool,
+    scrub_rules: Arc<scrub::ScrubSet>,
+    sinks: Arc<Vec<Arc<dyn outputs::Sink>>>,
+    cluster: Option<Arc<cluster::ClusterHandle>>,
+    health_registry: monitor::HealthRegistry,
+) -> Arc<dyn Fn(&config::MonitorConfig) -> Arc<dyn Fn() -> monitor::Monitor + Send + Sync> + Send + Sync>
+{
+    Arc::new(move |mon_cfg: &config::MonitorConfig| {
+        let mon_cfg = mon_cfg.clone();
+        let collector = collector.clone();
+        let scrub_rules = scrub_rules.clone();
+        let sinks = sinks.clone();
+        let cluster = cluster.clone();
+        let health = health_handle(&health_registry, &mon_cfg.name);
+        Arc::new(move || {
+            match build_monitor(&mon_cfg, collector.clone(), verbose, oneshot, scrub_messages, scrub_rules.clone(), sinks.clone(), cluster.clone(), health.clone()) {
+                Ok(m) => m,
+                Err(e) => {
+                    tracing::error!("Failed to build monitor '{}': {}", mon_cfg.name, e);
+                    never_matching_monitor(&mon_cfg, collector.clone(), verbose, oneshot, scrub_messages, scrub_rules.clone(), sinks.clone(), cluster.clone(), health.clone())
+                }
+            }
+        }) as Arc<dyn Fn() -> monitor::Monitor + Send + Sync>
+    })
+}
+
+/// Builds a fresh source, detector, and `Monitor` from `mon_cfg`. Used both for the
+/// initial start and, via `manager::MonitorManager`, to rebuild a monitor whose task
+/// has crashed.
+fn build_monitor(
+    mon_cfg: &config::MonitorConfig,
+    collector: Arc<sysstat::Collector>,
+    verbose: bool,
+    oneshot: bool,
+    scrub_messages: bool,
+    scrub_rules: Arc<scrub::ScrubSet>,
+    sinks: Arc<Vec<Arc<dyn outputs::Sink>>>,
+    cluster: Option<Arc<cluster::ClusterHandle>>,
+    health: Arc<Mutex<monitor::MonitorHealth>>,
+) -> Result<monitor::Monitor> {
+    let source: Box<dyn sources::LogSource> = match mon_cfg.monitor_type.as_str() {
+        "file" => {
+            if mon_cfg.path.is_empty() {
+                anyhow::bail!("path is empty");
+            }
+            Box::new(sources::file::FileSource::with_follow(
+                mon_cfg.name.clone(),
+                PathBuf::from(&mon_cfg.path),
+                mon_cfg.follow,
+            ))
+        }
+        "journalctl" => Box::new(sources::journalctl::JournalctlSource::new(
+            mon_cfg.name.clone(),
+            &mon_cfg.args,
+        )),
+        "dmesg" => Box::new(sources::dmesg::DmesgSource::new(mon_cfg.name.clone())),
+        "command" => {
+            let parts: Vec<&str> = mon_cfg.args.split_whitespace().collect();
+            if parts.is_empty() {
+                anyhow::bail!("command is empty");
+            }
+            let cmd = parts[0].to_string();
+            let args: Vec<String> = parts[1..].iter().map(|s| s.to_string()).collect();
+            Box::new(sources::command::CommandSource::new(
+                mon_cfg.name.clone(),
+                cmd,
+                args,
+            ))
+        }
+        "syslog" => Box::new(sources::syslog::SyslogSource::new(
+            mon_cfg.name.clone(),
+            mon_cfg.path.clone(),
+        )),
+        other => anyhow::bail!("unknown monitor type: {}", other),
+    };
+
+    let detector_format = determine_detector_format(mon_cfg);
+    let detector = detectors::get_detector(&detector_format, &mon_cfg.pattern)
+        .with_context(|| format!("failed to create detector for monitor '{}'", mon_cfg.name))?;
+
+    Ok(monitor::Monitor::new(
+        source,
+        detector,
+        collector,
+        verbose,
+        oneshot,
+        Some(mon_cfg.exclude_pattern.clone()),
+        mon_cfg.rate_limit_burst,
+        mon_cfg.rate_limit_window.clone(),
+        scrub_messages,
+        scrub_rules,
+        mon_cfg.context_lines,
+        sinks,
+        cluster,
+        health,
+    ))
+}
+
+/// Falls back to a monitor whose detector never matches anything, so a monitor that
+/// fails to rebuild after a crash (e.g. a now-invalid regex) doesn't crash-loop forever;
+/// it just sits idle until the config is fixed and reloaded.
+fn never_matching_monitor(
+    mon_cfg: &config::MonitorConfig,
+    collector: Arc<sysstat::Collector>,
+    verbose: bool,
+    oneshot: bool,
+    scrub_messages: bool,
+    scrub_rules: Arc<scrub::ScrubSet>,
+    sinks: Arc<Vec<Arc<dyn outputs::Sink>>>,
+    cluster: Option<Arc<cluster::ClusterHandle>>,
+    health: Arc<Mutex<monitor::MonitorHealth>>,
+) -> monitor::Monitor {
+    let source: Box<dyn sources::LogSource> = Box::new(sources::NullSource::new(mon_cfg.name.clone()));
+    let detector = detectors::get_detector("custom", "$^").expect("static pattern is valid");
+
+    monitor::Monitor::new(
+        source,
+        detector,
+        collector,
+        verbose,
+        oneshot,
+        None,
+        None,
+        None,
+        scrub_messages,
+        scrub_rules,
+        None,
+        sinks,
+        cluster,
+        health,
+    )
+}
+
 fn determine_detector_format(mon_cfg: &config::MonitorConfig) -> String {
     if !mon_cfg.format.is_empty() {
         return mon_cfg.format.clone();
@@ -356,6 +608,129 @@ fn format_instance_table(instances: &[ipc::StatusResponse], now: SystemTime) ->
     output
 }
 
+struct HealthRow {
+    pid: String,
+    monitor: String,
+    status: String,
+    last_read: String,
+    matched: String,
+    excluded: String,
+    dropped: String,
+    alive: String,
+}
+
+/// Flattens every instance's per-monitor health into one table (reusing the
+/// width-alignment approach from `format_instance_table`), one row per monitor.
+fn format_health_table(responses: &[ipc::HealthResponse]) -> String {
+    if responses.is_empty() {
+        return "No running instances found.\n".to_string();
+    }
+
+    let now = SystemTime::now();
+    let mut rows = Vec::new();
+
+    for resp in responses {
+        for m in &resp.monitors {
+            let last_read = match m.health.last_read {
+                Some(t) => format!(
+                    "{} ago",
+                    format_duration(now.duration_since(t).unwrap_or_default().as_secs())
+                ),
+                None => "-".to_string(),
+            };
+            let alive = match m.health.process_alive {
+                Some(true) => "yes".to_string(),
+                Some(false) => "no".to_string(),
+                None => "-".to_string(),
+            };
+
+            rows.push(HealthRow {
+                pid: resp.pid.to_string(),
+                monitor: m.name.clone(),
+                status: m.status.to_string(),
+                last_read,
+                matched: m.health.lines_matched.to_string(),
+                excluded: m.health.lines_excluded.to_string(),
+                dropped: m.health.rate_limit_drops.to_string(),
+                alive,
+            });
+        }
+    }
+
+    let headers = [
+        "PID",
+        "MONITOR",
+        "STATUS",
+        "LAST READ",
+        "MATCHED",
+        "EXCLUDED",
+        "DROPPED",
+        "ALIVE",
+    ];
+    let mut widths = [
+        headers[0].len(),
+        headers[1].len(),
+        headers[2].len(),
+        headers[3].len(),
+        headers[4].len(),
+        headers[5].len(),
+        headers[6].len(),
+    ];
+
+    for row in &rows {
+        widths[0] = max(widths[0], row.pid.len());
+        widths[1] = max(widths[1], row.monitor.len());
+        widths[2] = max(widths[2], row.status.len());
+        widths[3] = max(widths[3], row.last_read.len());
+        widths[4] = max(widths[4], row.matched.len());
+        widths[5] = max(widths[5], row.excluded.len());
+        widths[6] = max(widths[6], row.dropped.len());
+    }
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "{:<w0$} {:<w1$} {:<w2$} {:<w3$} {:<w4$} {:<w5$} {:<w6$} {}\n",
+        headers[0],
+        headers[1],
+        headers[2],
+        headers[3],
+        headers[4],
+        headers[5],
+        headers[6],
+        headers[7],
+        w0 = widths[0],
+        w1 = widths[1],
+        w2 = widths[2],
+        w3 = widths[3],
+        w4 = widths[4],
+        w5 = widths[5],
+        w6 = widths[6],
+    ));
+
+    for row in rows {
+        output.push_str(&format!(
+            "{:<w0$} {:<w1$} {:<w2$} {:<w3$} {:<w4$} {:<w5$} {:<w6$} {}\n",
+            row.pid,
+            row.monitor,
+            row.status,
+            row.last_read,
+            row.matched,
+            row.excluded,
+            row.dropped,
+            row.alive,
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+            w5 = widths[5],
+            w6 = widths[6],
+        ));
+    }
+
+    output
+}
+
 fn format_details(config: Option<&config::Config>) -> String {
     let Some(cfg) = config else {
         return "-".to_string();
@@ -429,12 +804,23 @@ mod tests {
                 exclude_pattern: String::new(),
                 rate_limit_burst: None,
                 rate_limit_window: None,
+                follow: false,
+                context_lines: None,
             }],
+            log_filter: "warn".to_string(),
             verbose: false,
             oneshot: false,
+            scrub_messages: true,
+            scrub_rules: Vec::new(),
+            outputs: config::OutputsConfig::default(),
             status: false,
             update: false,
+            health: false,
             metrics_port: 0,
+            config_path: None,
+            cluster: None,
+            config_sources: Vec::new(),
+            config_refresh_interval: None,
         }
     }
 
@@ -471,4 +857,46 @@ mod tests {
         assert_eq!(header_monitors_idx, first_monitors_idx);
         assert_eq!(header_monitors_idx, second_monitors_idx);
     }
+
+    #[test]
+    fn format_health_table_aligns_status_column() {
+        use crate::ipc::{HealthResponse, HealthStatus, MonitorHealthRecord};
+        use crate::monitor::MonitorHealth;
+
+        let responses = vec![HealthResponse {
+            pid: 9,
+            status: HealthStatus::Degraded,
+            monitors: vec![
+                MonitorHealthRecord {
+                    name: "alpha".to_string(),
+                    health: MonitorHealth::default(),
+                    status: HealthStatus::Ok,
+                },
+                MonitorHealthRecord {
+                    name: "beta".to_string(),
+                    health: MonitorHealth {
+                        rate_limit_drops: 3,
+                        ..MonitorHealth::default()
+                    },
+                    status: HealthStatus::Degraded,
+                },
+            ],
+        }];
+
+        let output = format_health_table(&responses);
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines.len() >= 3);
+
+        let header_status_idx = lines[0].find("STATUS").expect("header status");
+        let first_status_idx = lines[1].find("OK").expect("first status");
+        let second_status_idx = lines[2].find("DEGRADED").expect("second status");
+
+        assert_eq!(header_status_idx, first_status_idx);
+        assert_eq!(header_status_idx, second_status_idx);
+    }
+
+    #[test]
+    fn format_health_table_reports_no_instances() {
+        assert_eq!(format_health_table(&[]), "No running instances found.\n");
+    }
 }
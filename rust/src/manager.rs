@@ -0,0 +1,272 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::{Mutex, Notify};
+use tokio::task::JoinHandle;
+
+use crate::config::MonitorConfig;
+use crate::monitor::{self, Monitor, MonitorHandleRegistry};
+
+/// A cheap cooperative-cancellation flag for a single supervised monitor, so it can be
+/// stopped by `reconcile` without disturbing any of the others sharing the same
+/// `MonitorManager`.
+#[derive(Clone)]
+struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    notify: Arc<Notify>,
+}
+
+impl CancelToken {
+    fn new() -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    async fn cancelled(&self) {
+        if self.cancelled.load(Ordering::SeqCst) {
+            return;
+        }
+        self.notify.notified().await;
+    }
+}
+
+struct Entry {
+    mon_cfg: MonitorConfig,
+    cancel: CancelToken,
+    handle: JoinHandle<()>,
+}
+
+#[derive(Default)]
+struct Inner {
+    monitors: HashMap<String, Entry>,
+}
+
+/// Supervises a set of monitors, each running on its own task, and restarts any that
+/// crash (panic) instead of letting the whole process lose that source silently.
+/// Transient stream failures are already retried with backoff inside `Monitor::start`
+/// itself; `MonitorManager` only has to step in when the task dies outright.
+///
+/// The running set can also be reconciled against a freshly-reloaded config at runtime
+/// via `reconcile`, driven by the IPC `/update` signal (see `ipc::start_server`), without
+/// disturbing any monitor whose config didn't change.
+#[derive(Clone)]
+pub struct MonitorManager {
+    inner: Arc<Mutex<Inner>>,
+    shutdown: Arc<Notify>,
+    /// Published into by `supervise` itself, right after building the `Monitor` it's
+    /// about to run, so it always reflects the handle of the instance actually
+    /// processing log lines rather than one built and discarded elsewhere.
+    handles: MonitorHandleRegistry,
+}
+
+impl MonitorManager {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner::default())),
+            shutdown: Arc::new(Notify::new()),
+            handles: Arc::new(std::sync::Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// The shared registry of live monitor handles, for `config::ConfigWatcher` to read
+    /// through on every reload.
+    pub fn handles(&self) -> MonitorHandleRegistry {
+        self.handles.clone()
+    }
+
+    /// Starts a monitor under `mon_cfg.name`, built from `factory`. The factory is called
+    /// once to start the monitor, and again each time its task crashes or `reconcile`
+    /// respawns it, so it must be able to rebuild a fresh `Monitor` from scratch.
+    pub async fn add<F>(&self, mon_cfg: MonitorConfig, factory: F)
+    where
+        F: Fn() -> Monitor + Send + Sync + 'static,
+    {
+        let mut inner = self.inner.lock().await;
+        self.spawn_entry(&mut inner, mon_cfg, Arc::new(factory));
+    }
+
+    /// Waits for every currently-running monitor to finish (normally only via
+    /// `shutdown`, or `stop_on_eof` on every monitor). Monitors started after this call
+    /// begins (e.g. by a concurrent `reconcile`) are waited on too.
+    pub async fn run_all(&self) {
+        loop {
+            {
+                let mut inner = self.inner.lock().await;
+                inner.monitors.retain(|_, entry| !entry.handle.is_finished());
+                if inner.monitors.is_empty() {
+                    break;
+                }
+            }
+
+            tokio::select! {
+                _ = self.shutdown.notified() => break,
+                _ = tokio::time::sleep(tokio::time::Duration::from_millis(500)) => {}
+            }
+        }
+    }
+
+    /// Signals every supervised monitor to stop restarting and waits for the current
+    /// attempt to close its source before returning.
+    pub async fn shutdown(&self) {
+        self.shutdown.notify_waiters();
+        let mut inner = self.inner.lock().await;
+        for (_, entry) in inner.monitors.drain() {
+            entry.cancel.cancel();
+            let _ = entry.handle.await;
+        }
+    }
+
+    /// Reconciles the running monitor set against a freshly-loaded `new_monitors` list:
+    /// starts monitors that are new, cancels and removes ones that disappeared, and
+    /// cancels and respawns (via `build_factory`) ones whose config changed. Monitors
+    /// whose config is unchanged are left running untouched, so reload is
+    /// non-disruptive.
+    pub async fn reconcile<F>(&self, new_monitors: &[MonitorConfig], build_factory: F)
+    where
+        F: Fn(&MonitorConfig) -> Arc<dyn Fn() -> Monitor + Send + Sync>,
+    {
+        let mut inner = self.inner.lock().await;
+
+        let new_names: HashSet<&str> = new_monitors.iter().map(|m| m.name.as_str()).collect();
+
+        let removed: Vec<String> = inner
+            .monitors
+            .keys()
+            .filter(|name| !new_names.contains(name.as_str()))
+            .cloned()
+            .collect();
+
+        for name in removed {
+            if let Some(entry) = inner.monitors.remove(&name) {
+                entry.cancel.cancel();
+                let _ = entry.handle.await;
+                self.handles.lock().unwrap().remove(&name);
+                tracing::info!("Reconcile: stopped removed monitor '{}'", name);
+            }
+        }
+
+        for mon_cfg in new_monitors {
+            match inner.monitors.get(&mon_cfg.name) {
+                Some(existing) if !existing.mon_cfg.needs_respawn(mon_cfg) => continue,
+                Some(_) => {
+                    let entry = inner.monitors.remove(&mon_cfg.name).expect("just matched");
+                    entry.cancel.cancel();
+                    let _ = entry.handle.await;
+                    tracing::info!("Reconcile: respawning changed monitor '{}'", mon_cfg.name);
+                }
+                None => {
+                    tracing::info!("Reconcile: starting new monitor '{}'", mon_cfg.name);
+                }
+            }
+
+            self.spawn_entry(&mut inner, mon_cfg.clone(), build_factory(mon_cfg));
+        }
+    }
+
+    fn spawn_entry(
+        &self,
+        inner: &mut Inner,
+        mon_cfg: MonitorConfig,
+        factory: Arc<dyn Fn() -> Monitor + Send + Sync>,
+    ) {
+        let name = mon_cfg.name.clone();
+        let cancel = CancelToken::new();
+        let shutdown = self.shutdown.clone();
+        let task_cancel = cancel.clone();
+        let handles = self.handles.clone();
+
+        let handle = tokio::spawn(Self::supervise(
+            name.clone(),
+            factory,
+            shutdown,
+            task_cancel,
+            handles,
+        ));
+
+        inner.monitors.insert(
+            name,
+            Entry {
+                mon_cfg,
+                cancel,
+                handle,
+            },
+        );
+    }
+
+    async fn supervise(
+        name: String,
+        factory: Arc<dyn Fn() -> Monitor + Send + Sync>,
+        shutdown: Arc<Notify>,
+        cancel: CancelToken,
+        handles: MonitorHandleRegistry,
+    ) {
+        let mut consecutive_failures: u32 = 0;
+
+        loop {
+            let factory = factory.clone();
+            let task_name = name.clone();
+            let task_handles = handles.clone();
+            let task_cancel = cancel.clone();
+            let mut task = tokio::spawn(async move {
+                let mut monitor = factory();
+                task_handles
+                    .lock()
+                    .unwrap()
+                    .insert(task_name.clone(), monitor.handle());
+                let result = tokio::select! {
+                    res = monitor.start() => res,
+                    _ = task_cancel.cancelled() => Ok(()),
+                };
+                if let Err(e) = monitor.close().await {
+                    tracing::error!("Error closing monitor '{}': {}", task_name, e);
+                }
+                result
+            });
+
+            tokio::select! {
+                _ = shutdown.notified() => {
+                    cancel.cancel();
+                    let _ = (&mut task).await;
+                    break;
+                }
+                _ = cancel.cancelled() => {
+                    let _ = (&mut task).await;
+                    break;
+                }
+                res = &mut task => {
+                    match res {
+                        Ok(Ok(())) => break, // Monitor::start() returned normally (stop_on_eof)
+                        Ok(Err(e)) => {
+                            tracing::error!("Monitor '{}' error: {}", name, e);
+                        }
+                        Err(e) if e.is_panic() => {
+                            tracing::error!("Monitor '{}' panicked, restarting: {}", name, e);
+                        }
+                        Err(e) => {
+                            tracing::error!("Monitor '{}' task was cancelled: {}", name, e);
+                            break;
+                        }
+                    }
+
+                    consecutive_failures = consecutive_failures.saturating_add(1);
+                    let delay = monitor::backoff_delay(consecutive_failures);
+                    tracing::info!("Restarting monitor '{}' in {:?}", name, delay);
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+}
+
+impl Default for MonitorManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
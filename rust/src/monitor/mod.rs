@@ -1,7 +1,12 @@
 use anyhow::Result;
 use once_cell::sync::Lazy;
+use rand::Rng;
 use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::time::SystemTime;
 use tokio::io::AsyncBufReadExt;
 use tokio::sync::Mutex;
 use tokio::time::{Duration, Instant};
@@ -12,58 +17,221 @@ use crate::sysstat::Collector;
 
 const MAX_BUFFER_SIZE: usize = 1000;
 const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_CONTEXT_LINES: usize = 20;
 
 static TIMESTAMP_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^\[\s*([0-9.]+)\]").unwrap()
 });
 
+/// Parses a rate-limit window string ("30s", "5m", "1h", "250ms") into a `Duration`,
+/// defaulting to zero (no limiting) if it isn't recognized. Also reused by `config` for
+/// the config-source refresh interval and for validating `MonitorConfig::rate_limit_window`
+/// at load time. Suffixes are checked longest-first so "250ms" isn't mistaken for "250m"
+/// plus a stray "s".
+pub(crate) fn parse_window(window: &str) -> Duration {
+    if let Some(val) = window.strip_suffix("ms") {
+        if let Ok(millis) = val.parse::<u64>() {
+            return Duration::from_millis(millis);
+        }
+    } else if let Some(val) = window.strip_suffix('h') {
+        if let Ok(hours) = val.parse::<u64>() {
+            return Duration::from_secs(hours * 3600);
+        }
+    } else if let Some(val) = window.strip_suffix('m') {
+        if let Ok(mins) = val.parse::<u64>() {
+            return Duration::from_secs(mins * 60);
+        }
+    } else if let Some(val) = window.strip_suffix('s') {
+        if let Ok(secs) = val.parse::<u64>() {
+            return Duration::from_secs(secs);
+        }
+    }
+    Duration::from_secs(0)
+}
+
+const BACKOFF_BASE: Duration = Duration::from_secs(1);
+const BACKOFF_CAP: Duration = Duration::from_secs(60);
+
+/// Computes a full-jitter exponential backoff delay for the given number of consecutive
+/// failures: `delay = random(0, min(cap, base * 2^n))`. Shared by the monitor's own
+/// stream-retry loop and by `manager::MonitorManager`'s crash-restart supervision.
+pub(crate) fn backoff_delay(consecutive_failures: u32) -> Duration {
+    let exp = consecutive_failures.min(6); // 2^6 * base already exceeds the cap
+    let max_delay = BACKOFF_BASE
+        .saturating_mul(1u32 << exp)
+        .min(BACKOFF_CAP);
+    let jitter_ms = rand::thread_rng().gen_range(0..=max_delay.as_millis() as u64);
+    Duration::from_millis(jitter_ms)
+}
+
+/// Continuous-refill token bucket: holds at most `capacity` tokens, refilling at
+/// `capacity / window` tokens per second. A burst of `capacity` events is let through
+/// immediately; sustained traffic above that rate settles into one event every
+/// `window / capacity`. `limit == 0` (unset) disables limiting entirely.
 struct RateLimiter {
-    limit: u32,
-    window: Duration,
-    count: u32,
-    window_start: Instant,
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+    /// Events dropped since the last one that was let through, reported on the next
+    /// admitted event as `suppressed_since_last` so a burst storm collapses into one
+    /// Sentry event with a count instead of flooding the project quota.
+    suppressed: u64,
 }
 
 impl RateLimiter {
     fn new(limit: u32, window: Duration) -> Self {
+        let capacity = limit as f64;
+        let refill_per_sec = if limit == 0 || window.is_zero() {
+            0.0
+        } else {
+            capacity / window.as_secs_f64()
+        };
+
         Self {
-            limit,
-            window,
-            count: 0,
-            window_start: Instant::now(),
+            capacity,
+            tokens: capacity,
+            refill_per_sec,
+            last_refill: Instant::now(),
+            suppressed: 0,
         }
     }
 
-    fn allow(&mut self) -> bool {
-        if self.limit == 0 {
-            return true;
+    /// Refills tokens for the elapsed time since the last check, then admits the event by
+    /// consuming one token if at least one is available. Returns the count of events
+    /// suppressed since the last admitted one (0 if none were), or `None` if this event
+    /// itself is being suppressed.
+    fn allow(&mut self) -> Option<u64> {
+        if self.capacity == 0.0 {
+            return Some(0);
         }
 
         let now = Instant::now();
-        if now.duration_since(self.window_start) > self.window {
-            self.window_start = now;
-            self.count = 0;
-        }
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.last_refill = now;
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
 
-        if self.count < self.limit {
-            self.count += 1;
-            true
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Some(std::mem::take(&mut self.suppressed))
         } else {
-            false
+            self.suppressed += 1;
+            None
         }
     }
 }
 
+/// Liveness and error counters for a single running monitor, published by its `Monitor`
+/// loop and read by `ipc::start_server`'s `/health` handler (modeled on Ceph's
+/// `MMonHealth` peer health reports).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MonitorHealth {
+    /// When the last line was read from the source, `None` before the first read.
+    pub last_read: Option<SystemTime>,
+    pub lines_matched: u64,
+    pub lines_excluded: u64,
+    pub rate_limit_drops: u64,
+    /// Whether the underlying child process is still running, for `command`/`journalctl`/
+    /// `dmesg` sources. `None` for sources with no child process (e.g. `file`, `syslog`).
+    pub process_alive: Option<bool>,
+}
+
+/// Registry of live health handles keyed by monitor name, shared between `main()`'s
+/// monitor factory (which creates or looks up a monitor's entry before each build/rebuild,
+/// so the same handle survives crash-restarts and reconcile respawns) and `ipc`'s
+/// `/health` handler (which only ever reads it). A plain `std::sync::Mutex` is enough
+/// since lookups are synchronous and brief; the counters underneath are updated through
+/// their own `tokio::sync::Mutex`.
+pub type HealthRegistry = Arc<std::sync::Mutex<HashMap<String, Arc<Mutex<MonitorHealth>>>>>;
+
+/// Registry of the currently-running `MonitorHandle` for each monitor name, published by
+/// `manager::MonitorManager` the moment it builds the `Monitor` it's about to start (not
+/// by whoever merely constructed one to seed this map, which would be a different, never-
+/// started instance). `config::ConfigWatcher` reads through this map on every reload so it
+/// always hot-swaps settings into the monitor actually processing log lines, including
+/// after a crash-restart or reconcile respawn replaces it.
+pub type MonitorHandleRegistry = Arc<std::sync::Mutex<HashMap<String, MonitorHandle>>>;
+
 pub struct Monitor {
     source: Box<dyn LogSource>,
-    detector: Box<dyn Detector>,
-    exclusion_detector: Option<Box<dyn Detector>>,
+    detector: Arc<Mutex<Box<dyn Detector>>>,
+    exclusion_detector: Arc<Mutex<Option<Box<dyn Detector>>>>,
     collector: Arc<Collector>,
     verbose: bool,
     stop_on_eof: bool,
+    scrub_messages: bool,
+    /// Declarative `before_send`-style scrub rules, applied after `scrub_messages`'
+    /// hardcoded secret redaction and before a matched line is buffered for Sentry.
+    scrub_rules: Arc<crate::scrub::ScrubSet>,
     buffer: Arc<Mutex<Vec<String>>>,
     last_activity: Arc<Mutex<Instant>>,
     rate_limiter: Arc<Mutex<RateLimiter>>,
+    /// Ring buffer of the most recent non-matching lines, used as Sentry breadcrumbs so a
+    /// captured event carries the log history immediately preceding it.
+    context_buffer: Arc<Mutex<VecDeque<String>>>,
+    context_capacity: usize,
+    /// Context snapshot captured at the first match of the currently-accumulating buffer,
+    /// consumed (and cleared) the next time that buffer is flushed to Sentry.
+    pending_context: Arc<Mutex<Vec<String>>>,
+    /// Local sinks (console/file/syslog) every aggregated message is fanned out to
+    /// alongside Sentry.
+    sinks: Arc<Vec<Arc<dyn crate::outputs::Sink>>>,
+    /// Cross-instance quorum handle; when set, every aggregated message is subject to
+    /// leader-coordinated dedup before being reported to Sentry.
+    cluster: Option<Arc<crate::cluster::ClusterHandle>>,
+    /// Liveness and error counters published for the `/health` IPC command.
+    health: Arc<Mutex<MonitorHealth>>,
+}
+
+/// A cheaply-cloneable set of handles onto a running `Monitor`'s detector, exclusion
+/// detector, and rate limiter, so a `config::ConfigWatcher` can hot-swap them without
+/// restarting the underlying log stream.
+#[derive(Clone)]
+pub struct MonitorHandle {
+    detector: Arc<Mutex<Box<dyn Detector>>>,
+    exclusion_detector: Arc<Mutex<Option<Box<dyn Detector>>>>,
+    rate_limiter: Arc<Mutex<RateLimiter>>,
+}
+
+impl MonitorHandle {
+    /// Rebuilds the detector, exclusion detector, and rate limiter from `mon_cfg` and
+    /// swaps them in. On a detector build failure the previous detector is left in place.
+    pub async fn reload(&self, mon_cfg: &crate::config::MonitorConfig) {
+        let format = if !mon_cfg.format.is_empty() {
+            mon_cfg.format.as_str()
+        } else {
+            "custom"
+        };
+
+        match crate::detectors::get_detector(format, &mon_cfg.pattern) {
+            Ok(new_detector) => *self.detector.lock().await = new_detector,
+            Err(e) => {
+                tracing::warn!(
+                    "Config reload: keeping previous detector for '{}': {}",
+                    mon_cfg.name,
+                    e
+                );
+                return;
+            }
+        }
+
+        let new_exclusion = if mon_cfg.exclude_pattern.is_empty() {
+            None
+        } else {
+            crate::detectors::get_detector("custom", &mon_cfg.exclude_pattern).ok()
+        };
+        *self.exclusion_detector.lock().await = new_exclusion;
+
+        let burst = mon_cfg.rate_limit_burst.unwrap_or(0);
+        let window = mon_cfg
+            .rate_limit_window
+            .as_deref()
+            .map(parse_window)
+            .unwrap_or_default();
+        *self.rate_limiter.lock().await = RateLimiter::new(burst, window);
+
+        tracing::info!("Reloaded config for monitor '{}'", mon_cfg.name);
+    }
 }
 
 impl Monitor {
@@ -76,6 +244,12 @@ impl Monitor {
         exclude_pattern: Option<String>,
         rate_limit_burst: Option<u32>,
         rate_limit_window: Option<String>,
+        scrub_messages: bool,
+        scrub_rules: Arc<crate::scrub::ScrubSet>,
+        context_lines: Option<usize>,
+        sinks: Arc<Vec<Arc<dyn crate::outputs::Sink>>>,
+        cluster: Option<Arc<crate::cluster::ClusterHandle>>,
+        health: Arc<Mutex<MonitorHealth>>,
     ) -> Self {
         let exclusion_detector = if let Some(pattern) = exclude_pattern {
             if !pattern.is_empty() {
@@ -89,32 +263,45 @@ impl Monitor {
         };
 
         let burst = rate_limit_burst.unwrap_or(0);
-        let mut window = Duration::from_secs(0);
-        if let Some(w) = rate_limit_window {
-            if let Some(val) = w.strip_suffix("s") {
-                if let Ok(secs) = val.parse::<u64>() {
-                    window = Duration::from_secs(secs);
-                }
-            } else if let Some(val) = w.strip_suffix("m") {
-                if let Ok(mins) = val.parse::<u64>() {
-                    window = Duration::from_secs(mins * 60);
-                }
-            }
-        }
+        let window = rate_limit_window.as_deref().map(parse_window).unwrap_or_default();
 
         Self {
             source,
-            detector,
-            exclusion_detector,
+            detector: Arc::new(Mutex::new(detector)),
+            exclusion_detector: Arc::new(Mutex::new(exclusion_detector)),
             collector,
             verbose,
             stop_on_eof,
+            scrub_messages,
+            scrub_rules,
             buffer: Arc::new(Mutex::new(Vec::new())),
             last_activity: Arc::new(Mutex::new(Instant::now())),
             rate_limiter: Arc::new(Mutex::new(RateLimiter::new(burst, window))),
+            context_buffer: Arc::new(Mutex::new(VecDeque::new())),
+            context_capacity: context_lines.unwrap_or(DEFAULT_CONTEXT_LINES),
+            pending_context: Arc::new(Mutex::new(Vec::new())),
+            sinks,
+            cluster,
+            health,
         }
     }
 
+    /// Returns a cloneable handle for hot-swapping this monitor's detector, exclusion
+    /// detector, and rate limiter while it keeps running.
+    pub fn handle(&self) -> MonitorHandle {
+        MonitorHandle {
+            detector: self.detector.clone(),
+            exclusion_detector: self.exclusion_detector.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+        }
+    }
+
+    /// Returns a cloneable handle onto this monitor's live health counters, for the
+    /// `/health` IPC command to read without disturbing the running monitor.
+    pub fn health(&self) -> Arc<Mutex<MonitorHealth>> {
+        self.health.clone()
+    }
+
     pub async fn start(&mut self) -> Result<()> {
         if self.verbose {
             tracing::info!("Starting monitor for {}", self.source.name());
@@ -126,7 +313,11 @@ impl Monitor {
         let source_name = self.source.name().to_string();
         let collector = self.collector.clone();
         let rate_limiter = self.rate_limiter.clone();
+        let pending_context = self.pending_context.clone();
+        let sinks = self.sinks.clone();
+        let cluster = self.cluster.clone();
         let verbose = self.verbose;
+        let health = self.health.clone();
 
         tokio::spawn(async move {
             loop {
@@ -142,40 +333,66 @@ impl Monitor {
                     let msg = buf.join("\n");
                     buf.clear();
                     drop(buf);
-                    Self::send_to_sentry(&source_name, &msg, Some(&collector), &rate_limiter, verbose).await;
+                    let context = std::mem::take(&mut *pending_context.lock().await);
+                    Self::send_to_sentry(&source_name, &msg, &context, Some(&collector), &rate_limiter, &sinks, &health, cluster.as_deref(), verbose).await;
                 }
             }
         });
 
+        let mut consecutive_failures: u32 = 0;
+
         loop {
-            let reader = match self.source.stream().await {
-                Ok(r) => r,
-                Err(e) => {
-                    tracing::error!("Error starting source {}: {}", self.source.name(), e);
-                    tokio::time::sleep(Duration::from_secs(1)).await;
-                    continue;
-                }
-            };
+            let mut read_any = false;
+
+            match self.source.stream().await {
+                Ok(reader) => {
+                    if let Some(alive) = self.source.is_alive() {
+                        self.health.lock().await.process_alive = Some(alive);
+                    }
+
+                    let mut lines = reader.lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        read_any = true;
+                        consecutive_failures = 0;
+                        self.health.lock().await.last_read = Some(SystemTime::now());
+
+                        let line_bytes = line.as_bytes();
+                        let is_match = self.detector.lock().await.detect(line_bytes);
+                        if is_match {
+                            let is_excluded = match &*self.exclusion_detector.lock().await {
+                                Some(ed) => ed.detect(line_bytes),
+                                None => false,
+                            };
+                            if is_excluded {
+                                if self.verbose {
+                                    tracing::info!(
+                                        "[{}] Excluded: {}",
+                                        self.source.name(),
+                                        line
+                                    );
+                                }
+                                self.health.lock().await.lines_excluded += 1;
+                                continue;
+                            }
 
-            let mut lines = reader.lines();
-            while let Ok(Some(line)) = lines.next_line().await {
-                let line_bytes = line.as_bytes();
-                if self.detector.detect(line_bytes) {
-                    if let Some(ed) = &self.exclusion_detector {
-                        if ed.detect(line_bytes) {
                             if self.verbose {
-                                tracing::info!("[{}] Excluded: {}", self.source.name(), line);
+                                tracing::info!("[{}] Matched: {}", self.source.name(), line);
                             }
-                            continue;
+                            self.health.lock().await.lines_matched += 1;
+                            self.process_match(line).await;
+                        } else {
+                            self.push_context_line(line).await;
                         }
                     }
 
-                    if self.verbose {
-                        tracing::info!("[{}] Matched: {}", self.source.name(), line);
+                    if let Some(alive) = self.source.is_alive() {
+                        self.health.lock().await.process_alive = Some(alive);
                     }
-                    self.process_match(line).await;
                 }
-            }
+                Err(e) => {
+                    tracing::error!("Error starting source {}: {}", self.source.name(), e);
+                }
+            };
 
             // Flush remaining buffer
             self.force_flush().await;
@@ -187,26 +404,69 @@ impl Monitor {
                 break;
             }
 
+            if !read_any {
+                consecutive_failures = consecutive_failures.saturating_add(1);
+            }
+            let delay = backoff_delay(consecutive_failures);
+
             if self.verbose {
-                tracing::info!("Monitor for {} stopped, restarting in 1s...", self.source.name());
+                tracing::info!(
+                    "Monitor for {} stopped, restarting in {:?}...",
+                    self.source.name(),
+                    delay
+                );
             }
-            tokio::time::sleep(Duration::from_secs(1)).await;
+            tokio::time::sleep(delay).await;
         }
 
         Ok(())
     }
 
+    /// Records a non-matching line into the context ring buffer, evicting the oldest
+    /// entry once `context_capacity` is reached.
+    async fn push_context_line(&self, line: String) {
+        let mut context = self.context_buffer.lock().await;
+        if context.len() >= self.context_capacity {
+            context.pop_front();
+        }
+        context.push_back(line);
+    }
+
     async fn process_match(&self, line: String) {
         *self.last_activity.lock().await = Instant::now();
 
+        let line = if self.scrub_messages {
+            crate::sysstat::sanitizer::sanitize_message(&line)
+        } else {
+            line
+        };
+
+        let line = match self.scrub_rules.apply(&line) {
+            Some(line) => line,
+            None => {
+                if self.verbose {
+                    tracing::info!(
+                        "[{}] Dropped by scrub rule (drop_if_matched).",
+                        self.source.name()
+                    );
+                }
+                return;
+            }
+        };
+
         let mut buffer = self.buffer.lock().await;
+        if buffer.is_empty() {
+            let snapshot: Vec<String> = self.context_buffer.lock().await.iter().cloned().collect();
+            *self.pending_context.lock().await = snapshot;
+        }
         buffer.push(line);
 
         if buffer.len() >= MAX_BUFFER_SIZE {
             let msg = buffer.join("\n");
             buffer.clear();
             drop(buffer);
-            Self::send_to_sentry(self.source.name(), &msg, Some(&self.collector), &self.rate_limiter, self.verbose).await;
+            let context = std::mem::take(&mut *self.pending_context.lock().await);
+            Self::send_to_sentry(self.source.name(), &msg, &context, Some(&self.collector), &self.rate_limiter, &self.sinks, &self.health, self.cluster.as_deref(), self.verbose).await;
         }
     }
 
@@ -216,21 +476,53 @@ impl Monitor {
             let msg = buffer.join("\n");
             buffer.clear();
             drop(buffer);
-            Self::send_to_sentry(self.source.name(), &msg, Some(&self.collector), &self.rate_limiter, self.verbose).await;
+            let context = std::mem::take(&mut *self.pending_context.lock().await);
+            Self::send_to_sentry(self.source.name(), &msg, &context, Some(&self.collector), &self.rate_limiter, &self.sinks, &self.health, self.cluster.as_deref(), self.verbose).await;
         }
     }
 
-    async fn send_to_sentry(source_name: &str, message: &str, collector: Option<&Collector>, rate_limiter: &Mutex<RateLimiter>, verbose: bool) {
-        {
+    async fn send_to_sentry(
+        source_name: &str,
+        message: &str,
+        context: &[String],
+        collector: Option<&Collector>,
+        rate_limiter: &Mutex<RateLimiter>,
+        sinks: &[Arc<dyn crate::outputs::Sink>],
+        health: &Mutex<MonitorHealth>,
+        cluster: Option<&crate::cluster::ClusterHandle>,
+        verbose: bool,
+    ) {
+        let suppressed_since_last = {
             let mut limiter = rate_limiter.lock().await;
-            if !limiter.allow() {
+            match limiter.allow() {
+                Some(suppressed) => suppressed,
+                None => {
+                    health.lock().await.rate_limit_drops += 1;
+                    if verbose {
+                        tracing::info!("[{}] Rate limited, dropping event.", source_name);
+                    }
+                    return;
+                }
+            }
+        };
+
+        if let Some(cluster) = cluster {
+            let hash = crate::cluster::hash_line(message);
+            if !cluster.admit(source_name, hash).await {
                 if verbose {
-                    tracing::info!("[{}] Rate limited, dropping event.", source_name);
+                    tracing::info!(
+                        "[{}] Suppressed by cluster dedup (another instance already reported it).",
+                        source_name
+                    );
                 }
                 return;
             }
         }
 
+        for sink in sinks {
+            sink.emit(source_name, message).await;
+        }
+
         let state_json = if let Some(c) = collector {
             let state = c.get_state().await;
             serde_json::to_value(state).ok()
@@ -251,9 +543,31 @@ impl Monitor {
 
                 scope.set_extra("raw_line", serde_json::json!(message));
 
+                if suppressed_since_last > 0 {
+                    scope.set_extra("suppressed_since_last", serde_json::json!(suppressed_since_last));
+                }
+
                 if let Some(json) = state_json {
                     scope.set_extra("Server State", json);
                 }
+
+                // Attach the preceding non-matching lines as ordered breadcrumbs so the
+                // captured event carries the log history leading up to it.
+                for ctx_line in context {
+                    let mut data = std::collections::BTreeMap::new();
+                    if let Some(caps) = TIMESTAMP_REGEX.captures(ctx_line) {
+                        if let Some(ts) = caps.get(1) {
+                            data.insert("log_timestamp".to_string(), serde_json::json!(ts.as_str()));
+                        }
+                    }
+
+                    sentry::add_breadcrumb(sentry::Breadcrumb {
+                        category: Some("log".to_string()),
+                        message: Some(ctx_line.clone()),
+                        data,
+                        ..Default::default()
+                    });
+                }
             },
             || {
                 sentry::capture_message(message, sentry::Level::Error);
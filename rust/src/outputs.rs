@@ -0,0 +1,185 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::io::Write;
+use std::sync::Arc;
+use tokio::net::UdpSocket;
+use tokio::sync::Mutex;
+
+use crate::config::OutputsConfig;
+
+/// A local destination for matched events, reported alongside (or instead of) Sentry. See
+/// `config::OutputsConfig` for how these are enabled from a config file or the CLI.
+#[async_trait]
+pub trait Sink: Send + Sync {
+    /// A short identifier for logging (e.g. "console", "file:/var/log/x").
+    fn describe(&self) -> String;
+
+    /// Emits one already-scrubbed matched event. `source_name` is the monitor that
+    /// produced it.
+    async fn emit(&self, source_name: &str, message: &str);
+}
+
+/// Writes matched events to stdout, one per monitor flush.
+pub struct ConsoleSink;
+
+#[async_trait]
+impl Sink for ConsoleSink {
+    fn describe(&self) -> String {
+        "console".to_string()
+    }
+
+    async fn emit(&self, source_name: &str, message: &str) {
+        println!("[{}] {}", source_name, message);
+    }
+}
+
+/// Appends matched events to a local file, in lonk's `log_rules` style.
+pub struct FileSink {
+    path: String,
+    file: Mutex<std::fs::File>,
+}
+
+impl FileSink {
+    pub fn open(path: &str) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("failed to open output file: {}", path))?;
+        Ok(Self {
+            path: path.to_string(),
+            file: Mutex::new(file),
+        })
+    }
+}
+
+#[async_trait]
+impl Sink for FileSink {
+    fn describe(&self) -> String {
+        format!("file:{}", self.path)
+    }
+
+    async fn emit(&self, source_name: &str, message: &str) {
+        let mut file = self.file.lock().await;
+        if let Err(e) = writeln!(file, "[{}] {}", source_name, message) {
+            tracing::warn!("failed to write to output file {}: {}", self.path, e);
+        }
+    }
+}
+
+/// Sends matched events as RFC 3164-style syslog datagrams to a remote target, reusing
+/// `sources::syslog::SyslogSource`'s `tcp:`/`udp:` address grammar (only `udp:` is
+/// supported for output, since syslog datagram delivery is inherently connectionless).
+pub struct SyslogSink {
+    facility: String,
+    target: String,
+    socket: UdpSocket,
+}
+
+impl SyslogSink {
+    pub async fn connect(facility: String, target: &str) -> Result<Self> {
+        let addr = target.strip_prefix("udp:").unwrap_or(target);
+        let socket = UdpSocket::bind("0.0.0.0:0")
+            .await
+            .context("failed to bind syslog output socket")?;
+        socket
+            .connect(addr)
+            .await
+            .with_context(|| format!("failed to connect syslog output socket to {}", addr))?;
+        Ok(Self {
+            facility,
+            target: target.to_string(),
+            socket,
+        })
+    }
+}
+
+#[async_trait]
+impl Sink for SyslogSink {
+    fn describe(&self) -> String {
+        format!("syslog:{}", self.target)
+    }
+
+    async fn emit(&self, source_name: &str, message: &str) {
+        let payload = format!(
+            "<{}>{}: {}",
+            facility_priority(&self.facility),
+            source_name,
+            message
+        );
+        if let Err(e) = self.socket.send(payload.as_bytes()).await {
+            tracing::warn!("failed to send to syslog output {}: {}", self.target, e);
+        }
+    }
+}
+
+/// RFC 3164 severity code for "error", used as every sink-emitted event's PRI severity.
+const SEVERITY_ERROR: u32 = 3;
+
+/// Maps a syslog facility name to its numeric code (RFC 3164 table 1) and combines it
+/// with `SEVERITY_ERROR` into a PRI value, defaulting to facility `user` (1) for an
+/// unrecognized name rather than rejecting it, since this only affects log routing.
+fn facility_priority(facility: &str) -> u32 {
+    let facility_code = match facility {
+        "kern" => 0,
+        "user" => 1,
+        "mail" => 2,
+        "daemon" => 3,
+        "auth" => 4,
+        "syslog" => 5,
+        "lpr" => 6,
+        "news" => 7,
+        "uucp" => 8,
+        "cron" => 9,
+        "authpriv" => 10,
+        "ftp" => 11,
+        "local0" => 16,
+        "local1" => 17,
+        "local2" => 18,
+        "local3" => 19,
+        "local4" => 20,
+        "local5" => 21,
+        "local6" => 22,
+        "local7" => 23,
+        _ => 1,
+    };
+    facility_code * 8 + SEVERITY_ERROR
+}
+
+/// Builds every sink enabled in `cfg`, in `console, file, syslog` order, for `main()` to
+/// compile once at startup and share across all monitors.
+pub async fn build_sinks(cfg: &OutputsConfig) -> Result<Vec<Arc<dyn Sink>>> {
+    let mut sinks: Vec<Arc<dyn Sink>> = Vec::new();
+
+    if cfg.console {
+        sinks.push(Arc::new(ConsoleSink));
+    }
+    if let Some(path) = &cfg.file {
+        sinks.push(Arc::new(FileSink::open(path)?));
+    }
+    if let Some(syslog) = &cfg.syslog {
+        sinks.push(Arc::new(
+            SyslogSink::connect(syslog.facility.clone(), &syslog.target).await?,
+        ));
+    }
+
+    Ok(sinks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn facility_priority_maps_known_facilities_to_error_severity() {
+        assert_eq!(facility_priority("user"), 8 + SEVERITY_ERROR);
+        assert_eq!(facility_priority("local0"), 16 * 8 + SEVERITY_ERROR);
+        assert_eq!(facility_priority("unknown"), 8 + SEVERITY_ERROR);
+    }
+
+    #[tokio::test]
+    async fn build_sinks_returns_empty_when_nothing_enabled() {
+        let sinks = build_sinks(&OutputsConfig::default()).await.unwrap();
+        assert!(sinks.is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One declarative scrub rule, modeled on Sentry's native `before_send` hook but driven by
+/// config instead of hardcoded: a regex applied to a matched log line, replaced with
+/// `replacement` (which may reference the pattern's named/numbered capture groups), or the
+/// whole event dropped entirely when `drop_if_matched` is set (e.g. a line that must never
+/// reach Sentry at all, rather than merely being redacted).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrubRule {
+    /// Human-readable label for logging (e.g. "email", "bearer-token").
+    pub name: String,
+    pub pattern: String,
+    /// Replacement template in `regex::Regex::replace_all` syntax (e.g. `"[EMAIL]"` or
+    /// `"${user}@[REDACTED]"`). Ignored when `drop_if_matched` is set.
+    #[serde(default = "default_replacement")]
+    pub replacement: String,
+    /// Discard the event entirely instead of redacting it.
+    #[serde(default)]
+    pub drop_if_matched: bool,
+}
+
+fn default_replacement() -> String {
+    "[REDACTED]".to_string()
+}
+
+/// A compiled, ordered set of `ScrubRule`s, applied to every matched log line before it
+/// becomes a Sentry event. Compiled once at startup from `Config::scrub_rules` so the
+/// monitor's hot path never re-parses a pattern.
+#[derive(Default)]
+pub struct ScrubSet {
+    rules: Vec<(Regex, String, bool)>,
+}
+
+impl ScrubSet {
+    pub fn compile(rules: &[ScrubRule]) -> Result<Self> {
+        let mut compiled = Vec::with_capacity(rules.len());
+        for rule in rules {
+            let regex = Regex::new(&rule.pattern)
+                .with_context(|| format!("invalid scrub pattern '{}' ({})", rule.pattern, rule.name))?;
+            compiled.push((regex, rule.replacement.clone(), rule.drop_if_matched));
+        }
+        Ok(Self { rules: compiled })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Applies every rule to `message` in order. Returns `None` as soon as a
+    /// `drop_if_matched` rule fires, telling the caller to discard the event entirely;
+    /// otherwise the progressively-redacted message.
+    pub fn apply(&self, message: &str) -> Option<String> {
+        let mut current = message.to_string();
+        for (pattern, replacement, drop_if_matched) in &self.rules {
+            if pattern.is_match(&current) {
+                if *drop_if_matched {
+                    return None;
+                }
+                current = pattern.replace_all(&current, replacement.as_str()).into_owned();
+            }
+        }
+        Some(current)
+    }
+}
+
+/// Loads a standalone rules file (a YAML list of `ScrubRule`) for the `--scrub` CLI flag
+/// in CLI-only mode, where there's no `FileConfig` to carry a `scrub_rules` section.
+pub fn load_rules_file(path: &Path) -> Result<Vec<ScrubRule>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read scrub rules file: {:?}", path))?;
+    serde_yaml::from_str(&content)
+        .with_context(|| format!("failed to parse scrub rules file: {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, pattern: &str, replacement: &str) -> ScrubRule {
+        ScrubRule {
+            name: name.to_string(),
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            drop_if_matched: false,
+        }
+    }
+
+    #[test]
+    fn apply_redacts_in_order() {
+        let rules = vec![
+            rule("email", r"[\w.+-]+@[\w-]+\.[\w.-]+", "[EMAIL]"),
+            rule("digits", r"\d{4,}", "[NUMBER]"),
+        ];
+        let set = ScrubSet::compile(&rules).unwrap();
+
+        let redacted = set.apply("contact alice@example.com, ref 123456").unwrap();
+        assert_eq!(redacted, "contact [EMAIL], ref [NUMBER]");
+    }
+
+    #[test]
+    fn apply_drops_event_on_drop_if_matched_rule() {
+        let rules = vec![ScrubRule {
+            name: "forbidden".to_string(),
+            pattern: "top-secret".to_string(),
+            replacement: default_replacement(),
+            drop_if_matched: true,
+        }];
+        let set = ScrubSet::compile(&rules).unwrap();
+
+        assert!(set.apply("this log line is top-secret").is_none());
+        assert_eq!(set.apply("this log line is fine").unwrap(), "this log line is fine");
+    }
+}
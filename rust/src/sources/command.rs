@@ -0,0 +1,60 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::io::{AsyncBufRead, BufReader};
+use tokio::process::{Child, Command};
+
+use super::LogSource;
+
+pub struct CommandSource {
+    name: String,
+    cmd: String,
+    args: Vec<String>,
+    process: Option<Child>,
+}
+
+impl CommandSource {
+    pub fn new(name: String, cmd: String, args: Vec<String>) -> Self {
+        Self {
+            name,
+            cmd,
+            args,
+            process: None,
+        }
+    }
+}
+
+#[async_trait]
+impl LogSource for CommandSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn stream(&mut self) -> Result<Box<dyn AsyncBufRead + Unpin + Send>> {
+        let mut child = Command::new(&self.cmd)
+            .args(&self.args)
+            .stdout(std::process::Stdio::piped())
+            .spawn()?;
+
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("Failed to capture stdout from {}", self.cmd))?;
+
+        self.process = Some(child);
+        Ok(Box::new(BufReader::new(stdout)))
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if let Some(mut child) = self.process.take() {
+            child.kill().await?;
+        }
+        Ok(())
+    }
+
+    fn is_alive(&mut self) -> Option<bool> {
+        match &mut self.process {
+            Some(child) => Some(matches!(child.try_wait(), Ok(None))),
+            None => Some(false),
+        }
+    }
+}
@@ -46,4 +46,11 @@ impl LogSource for DmesgSource {
         }
         Ok(())
     }
+
+    fn is_alive(&mut self) -> Option<bool> {
+        match &mut self.process {
+            Some(child) => Some(matches!(child.try_wait(), Ok(None))),
+            None => Some(false),
+        }
+    }
 }
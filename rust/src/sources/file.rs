@@ -1,19 +1,155 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use std::io;
+use std::os::unix::fs::MetadataExt;
 use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
 use tokio::fs::File;
-use tokio::io::{AsyncBufRead, BufReader};
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncSeekExt, BufReader, ReadBuf};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{error, warn};
 
 use super::LogSource;
 
+const POLL_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_millis(500);
+
 pub struct FileSource {
     name: String,
     path: PathBuf,
+    follow: bool,
+    /// Shared with the spawned `follow_loop` task (when `follow` is set) so the advanced
+    /// read position is visible here even after that task exits, letting a subsequent
+    /// `stream()` call (e.g. after `Monitor::start` restarts a crashed source) resume
+    /// instead of replaying the file from the beginning.
+    offset: Arc<AtomicU64>,
+    close_tx: Option<broadcast::Sender<()>>,
 }
 
 impl FileSource {
     pub fn new(name: String, path: PathBuf) -> Self {
-        Self { name, path }
+        Self::with_follow(name, path, false)
+    }
+
+    /// `follow` enables `tail -F` semantics: once EOF is reached the file is kept open
+    /// and appended data is awaited, while the path is periodically `stat`-ed to detect
+    /// rotation (inode change) or truncation (size shrinks below the read offset).
+    pub fn with_follow(name: String, path: PathBuf, follow: bool) -> Self {
+        Self {
+            name,
+            path,
+            follow,
+            offset: Arc::new(AtomicU64::new(0)),
+            close_tx: None,
+        }
+    }
+
+    /// Opens `path`, seeks to `pos`, and returns the open file plus its device/inode.
+    /// Retries on a transient failure (e.g. a rotation race where the new file hasn't
+    /// been created yet) rather than propagating the error, since a `follow_loop` that
+    /// exits on a transient reopen failure would either silently stop following or force
+    /// a full monitor restart. Returns `None` if `close_rx` fires while retrying.
+    async fn open_at(
+        path: &PathBuf,
+        pos: u64,
+        close_rx: &mut broadcast::Receiver<()>,
+    ) -> Option<(File, u64, u64)> {
+        loop {
+            match Self::try_open_at(path, pos).await {
+                Ok(opened) => return Some(opened),
+                Err(e) => {
+                    warn!("File source {:?}: failed to open, will retry: {}", path, e);
+                    tokio::select! {
+                        _ = close_rx.recv() => return None,
+                        _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    async fn try_open_at(path: &PathBuf, pos: u64) -> Result<(File, u64, u64)> {
+        let mut file = File::open(path).await?;
+        let meta = file.metadata().await?;
+        file.seek(io::SeekFrom::Start(pos)).await?;
+        Ok((file, meta.dev(), meta.ino()))
+    }
+
+    async fn follow_loop(
+        path: PathBuf,
+        offset: Arc<AtomicU64>,
+        tx: mpsc::Sender<Vec<u8>>,
+        mut close_rx: broadcast::Receiver<()>,
+    ) -> Result<()> {
+        let mut pos = offset.load(Ordering::SeqCst);
+        let (mut file, mut dev, mut ino) = match Self::open_at(&path, pos, &mut close_rx).await {
+            Some(opened) => opened,
+            None => return Ok(()),
+        };
+
+        let mut buf = vec![0u8; 64 * 1024];
+
+        loop {
+            tokio::select! {
+                _ = close_rx.recv() => {
+                    return Ok(());
+                }
+                n = file.read(&mut buf) => {
+                    let n = n?;
+                    if n > 0 {
+                        pos += n as u64;
+                        offset.store(pos, Ordering::SeqCst);
+                        if tx.send(buf[..n].to_vec()).await.is_err() {
+                            return Ok(());
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            // Reached EOF: wait a bit, then check whether the file was rotated or
+            // truncated before resuming from the saved offset.
+            tokio::select! {
+                _ = close_rx.recv() => {
+                    return Ok(());
+                }
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+            }
+
+            let meta = match tokio::fs::metadata(&path).await {
+                Ok(m) => m,
+                Err(e) => {
+                    warn!("File source {:?}: stat failed, will retry: {}", path, e);
+                    continue;
+                }
+            };
+
+            let rotated = meta.dev() != dev || meta.ino() != ino;
+            let truncated = meta.len() < pos;
+
+            if rotated || truncated {
+                if rotated {
+                    tracing::info!("File source {:?}: rotation detected, reopening", path);
+                } else {
+                    tracing::info!("File source {:?}: truncation detected, reopening", path);
+                }
+
+                pos = 0;
+                offset.store(pos, Ordering::SeqCst);
+                match Self::open_at(&path, pos, &mut close_rx).await {
+                    Some((f, d, i)) => {
+                        file = f;
+                        dev = d;
+                        ino = i;
+                    }
+                    None => return Ok(()),
+                }
+            } else {
+                file.seek(io::SeekFrom::Start(pos)).await?;
+            }
+        }
     }
 }
 
@@ -24,11 +160,76 @@ impl LogSource for FileSource {
     }
 
     async fn stream(&mut self) -> Result<Box<dyn AsyncBufRead + Unpin + Send>> {
-        let file = File::open(&self.path).await?;
-        Ok(Box::new(BufReader::new(file)))
+        if !self.follow {
+            let file = File::open(&self.path).await?;
+            return Ok(Box::new(BufReader::new(file)));
+        }
+
+        let (tx, rx) = mpsc::channel::<Vec<u8>>(100);
+        let (close_tx, close_rx) = broadcast::channel(1);
+        self.close_tx = Some(close_tx);
+
+        let path = self.path.clone();
+        let offset = self.offset.clone();
+
+        tokio::spawn(async move {
+            if let Err(e) = Self::follow_loop(path, offset, tx, close_rx).await {
+                error!("File follow error: {}", e);
+            }
+        });
+
+        let reader = ChannelReader {
+            rx,
+            current_chunk: None,
+        };
+
+        Ok(Box::new(BufReader::new(reader)))
     }
 
     async fn close(&mut self) -> Result<()> {
+        if let Some(tx) = self.close_tx.take() {
+            let _ = tx.send(());
+        }
         Ok(())
     }
 }
+
+/// Turns a channel of byte chunks into an `AsyncRead`, the same pattern used by
+/// `sources::syslog::ChannelReader`.
+struct ChannelReader {
+    rx: mpsc::Receiver<Vec<u8>>,
+    current_chunk: Option<io::Cursor<Vec<u8>>>,
+}
+
+impl AsyncRead for ChannelReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            if let Some(cursor) = &mut self.current_chunk {
+                let pos = cursor.position() as usize;
+                let inner = cursor.get_ref();
+                let remaining = inner.len() - pos;
+
+                if remaining > 0 {
+                    let to_read = std::cmp::min(remaining, buf.remaining());
+                    buf.put_slice(&inner[pos..pos + to_read]);
+                    cursor.set_position((pos + to_read) as u64);
+                    return Poll::Ready(Ok(()));
+                } else {
+                    self.current_chunk = None;
+                }
+            }
+
+            match self.rx.poll_recv(cx) {
+                Poll::Ready(Some(data)) => {
+                    self.current_chunk = Some(io::Cursor::new(data));
+                }
+                Poll::Ready(None) => return Poll::Ready(Ok(())), // EOF
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -49,4 +49,11 @@ impl LogSource for JournalctlSource {
         }
         Ok(())
     }
+
+    fn is_alive(&mut self) -> Option<bool> {
+        match &mut self.process {
+            Some(child) => Some(matches!(child.try_wait(), Ok(None))),
+            None => Some(false),
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
 
 pub mod file;
 pub mod journalctl;
@@ -16,4 +17,51 @@ pub trait LogSource: Send + Sync {
 
     /// Close the log source and release resources
     async fn close(&mut self) -> Result<()>;
+
+    /// Whether the underlying child process is still running, for sources backed by one
+    /// (`command`, `journalctl`, `dmesg`). `None` for sources with no process to check
+    /// (e.g. `file`, `syslog`).
+    fn is_alive(&mut self) -> Option<bool> {
+        None
+    }
+}
+
+/// A source that never produces any data. Used as a safe fallback when a real source
+/// fails to (re)build, so the monitor idles instead of crash-looping or spawning
+/// processes uselessly.
+pub struct NullSource {
+    name: String,
+}
+
+impl NullSource {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+struct Pending;
+
+impl AsyncRead for Pending {
+    fn poll_read(
+        self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+        _buf: &mut ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::task::Poll::Pending
+    }
+}
+
+#[async_trait]
+impl LogSource for NullSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn stream(&mut self) -> Result<Box<dyn AsyncBufRead + Unpin + Send>> {
+        Ok(Box::new(tokio::io::BufReader::new(Pending)))
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
 }
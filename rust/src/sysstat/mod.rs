@@ -1,3 +1,5 @@
+pub mod sanitizer;
+
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use sysinfo::System;